@@ -0,0 +1,64 @@
+/// The color names CLML tags use, in SGR order.
+static NAMES: [&str; 8] = [
+	"black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Renders the CLML color tags the bundled art is written in, for art that's
+/// loaded at runtime rather than compiled by `build.rs`.
+///
+/// * `<red>`, `<red-bg>`: a basic color, as the foreground or background.
+/// * `<255 208>`, `<255-bg 208>`: a 256-color palette index.
+/// * `<bold>`, `<dim>`, `<italic>`, `<underline>`, `<reset>`.
+///
+/// Anything else in angle brackets, like `<>`, is kept as text.
+pub(crate) fn render(s: &str) -> String {
+	let mut to_return = String::with_capacity(s.len());
+	let mut rest = s;
+	while let Some(start) = rest.find('<') {
+		to_return.push_str(&rest[..start]);
+		let after = &rest[start + 1..];
+		let tag = after.find(['<', '>'])
+			.filter(|i| after.as_bytes()[*i] == b'>')
+			.and_then(|end| sgr(&after[..end]).map(|params| (end, params)));
+		match tag {
+			Some((end, params)) => {
+				to_return.push_str(&format!("\u{1b}[{}m", params));
+				rest = &after[end + 1..];
+			}
+			None => {
+				to_return.push('<');
+				rest = after;
+			}
+		}
+	}
+	to_return.push_str(rest);
+	to_return
+}
+
+/// The SGR parameters for a tag, or `None` if it isn't one.
+fn sgr(tag: &str) -> Option<String> {
+	let attribute = match tag {
+		"reset" => Some(0),
+		"bold" => Some(1),
+		"dim" => Some(2),
+		"italic" => Some(3),
+		"underline" => Some(4),
+		_ => None,
+	};
+	if let Some(v) = attribute {
+		return Some(v.to_string());
+	}
+	if let Some((kind, index)) = tag.split_once(' ') {
+		let index = index.parse::<u8>().ok()?;
+		return match kind {
+			"255" => Some(format!("38;5;{}", index)),
+			"255-bg" => Some(format!("48;5;{}", index)),
+			_ => None,
+		};
+	}
+	let (name, base) = match tag.strip_suffix("-bg") {
+		Some(name) => (name, 40),
+		None => (tag, 30),
+	};
+	NAMES.iter().position(|n| *n == name).map(|n| (base + n).to_string())
+}
@@ -0,0 +1,142 @@
+use crate::color;
+use crate::unicode_width;
+use crate::utils;
+
+use std::convert::{ TryFrom };
+
+use serde::Deserialize;
+use unicode_width::{ UnicodeWidthChar };
+
+use color::{ Color };
+use utils::ansi;
+
+/// A coloring pass over the art, which replaces its own colors.
+///
+/// Parsed from `gradient:COLORS` (left to right), `vgradient:COLORS` (top to
+/// bottom), `rainbow` or `palette` (the distro's colors, one per line).
+/// `COLORS` is two or more colors, separated by commas, in any form `Color`
+/// takes other than `default`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub(crate) enum Effect {
+	Gradient { stops: Vec<(u8, u8, u8)>, vertical: bool },
+	Rainbow,
+	Palette,
+}
+
+impl Effect {
+	pub fn parse(s: &str) -> Result<Self, String> {
+		let (name, args) = match s.split_once(':') {
+			Some((name, args)) => (name, Some(args)),
+			None => (s, None),
+		};
+		match (name.trim().to_lowercase().as_str(), args) {
+			("gradient", Some(args)) | ("vgradient", Some(args)) => {
+				let stops = args.split(',')
+					.map(|v| {
+						let color = Color::parse(v.trim())?;
+						color.rgb().ok_or_else(|| format!("\"{}\" has no RGB value to blend.", v.trim()))
+					})
+					.collect::<Result<Vec<(u8, u8, u8)>, String>>()?;
+				if stops.len() < 2 {
+					return Err(String::from("A gradient needs at least two colors."));
+				}
+				Ok(Effect::Gradient { stops, vertical: name.trim().eq_ignore_ascii_case("vgradient") })
+			}
+			("gradient", None) | ("vgradient", None) => Err(format!("Expected colors, as in \"{}:#ff0080,#00ffcc\".", name)),
+			("rainbow", None) => Ok(Effect::Rainbow),
+			("palette", None) => Ok(Effect::Palette),
+			_ => Err(format!("Unknown art effect \"{}\". Expected gradient:COLORS, vgradient:COLORS, rainbow or palette.", s)),
+		}
+	}
+
+	/// Recolors `art`, which keeps its shape but loses its own colors.
+	/// `palette` is the distro's colors, for `Palette`.
+	pub fn apply(&self, art: &str, palette: &[Color]) -> String {
+		let plain = ansi::strip(art);
+		let lines: Vec<&str> = plain.split('\n').collect();
+		let width = lines.iter().map(|v| ansi::width(v)).max().unwrap_or(0);
+		let height = lines.len();
+		let mut to_return = String::with_capacity(plain.len() * 8);
+		for (y, line) in lines.iter().enumerate() {
+			if y > 0 { to_return.push('\n'); }
+			let mut x = 0;
+			for c in line.chars() {
+				if !c.is_whitespace() {
+					to_return.push_str(&self.escape(x, y, width, height, palette));
+				}
+				to_return.push(c);
+				x += c.width().unwrap_or(0);
+			}
+			to_return.push_str("\u{1b}[0m");
+		}
+		to_return
+	}
+
+	/// The escape sequence for the cell at `x`, `y` in art `width` by
+	/// `height` cells.
+	fn escape(&self, x: usize, y: usize, width: usize, height: usize, palette: &[Color]) -> String {
+		let (r, g, b) = match self {
+			Effect::Gradient { stops, vertical } => {
+				let (at, of) = if *vertical { (y, height) } else { (x, width) };
+				blend(stops, at as f64 / of.saturating_sub(1).max(1) as f64)
+			}
+			// Diagonal bands, like lolcat.
+			Effect::Rainbow => hue(((x + y * 2) * 12 % 360) as f64),
+			Effect::Palette => {
+				let color = palette.get(y % palette.len().max(1)).copied().unwrap_or(Color::DEFAULT);
+				return color.escape();
+			}
+		};
+		format!("\u{1b}[38;2;{};{};{}m", r, g, b)
+	}
+}
+
+impl TryFrom<String> for Effect {
+	type Error = String;
+
+	fn try_from(v: String) -> Result<Self, String> {
+		Effect::parse(&v)
+	}
+}
+
+/// The color `t` of the way along `stops`, from 0 to 1.
+fn blend(stops: &[(u8, u8, u8)], t: f64) -> (u8, u8, u8) {
+	let t = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+	let i = (t.floor() as usize).min(stops.len() - 2);
+	let (a, b, t) = (stops[i], stops[i + 1], t - i as f64);
+	let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+	(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
+
+/// A fully saturated color, `degrees` around the color wheel.
+fn hue(degrees: f64) -> (u8, u8, u8) {
+	let h = degrees / 60.0;
+	let x = ((1.0 - (h % 2.0 - 1.0).abs()) * 255.0).round() as u8;
+	match h as u32 {
+		0 => (255, x, 0),
+		1 => (x, 255, 0),
+		2 => (0, 255, x),
+		3 => (0, x, 255),
+		4 => (x, 0, 255),
+		_ => (255, 0, x),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn applies_gradients() {
+		let effect = Effect::parse("gradient:#000000, #ffffff").unwrap();
+		assert_eq!(effect.apply("\u{1b}[31mab c", &[]), "\u{1b}[38;2;0;0;0ma\u{1b}[38;2;85;85;85mb \u{1b}[38;2;255;255;255mc\u{1b}[0m");
+		let vertical = Effect::parse("vgradient:#ff0000,#0000ff,#00ff00").unwrap();
+		assert_eq!(vertical.escape(0, 1, 1, 3, &[]), "\u{1b}[38;2;0;0;255m");
+		assert_eq!(Effect::parse("palette").unwrap().apply("a\nb\nc", &[Color::indexed(1), Color::indexed(2)]),
+			"\u{1b}[38;5;1ma\u{1b}[0m\n\u{1b}[38;5;2mb\u{1b}[0m\n\u{1b}[38;5;1mc\u{1b}[0m");
+		assert!(Effect::parse("gradient:red").is_err());
+		assert!(Effect::parse("gradient:red,default").is_err());
+		assert!(Effect::parse("sparkles").is_err());
+	}
+}
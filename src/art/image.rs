@@ -0,0 +1,139 @@
+use crate::image;
+
+use crate::errors;
+
+use std::path::{ Path, PathBuf };
+
+use serde::Deserialize;
+use viuer::{ Config, KittySupport };
+
+/// How an image logo is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Protocol {
+	/// Kitty's, iTerm's or Sixel, whichever the terminal supports, and
+	/// otherwise the ASCII art.
+	#[default]
+	Auto,
+	Kitty,
+	Iterm,
+	Sixel,
+	/// Colored half blocks, which work in any terminal with truecolor.
+	Blocks,
+}
+
+impl Protocol {
+	/// The protocol to draw with. `Auto` becomes whichever the terminal
+	/// supports, or `None` if it doesn't support any.
+	fn detect(self) -> errors::Result<Option<Protocol>> {
+		match self {
+			Protocol::Auto => Ok(if viuer::get_kitty_support() != KittySupport::None {
+				Some(Protocol::Kitty)
+			} else if viuer::is_iterm_supported() {
+				Some(Protocol::Iterm)
+			} else if sixel_supported() {
+				Some(Protocol::Sixel)
+			} else {
+				None
+			}),
+			Protocol::Sixel if !cfg!(feature = "sixel") => Err(errors::FreshfetchError::General(String::from(
+				"This freshfetch was built without Sixel support. Rebuild it with `--features sixel`."))),
+			v => Ok(Some(v)),
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			Protocol::Auto => "auto",
+			Protocol::Kitty => "kitty",
+			Protocol::Iterm => "iTerm",
+			Protocol::Sixel => "sixel",
+			Protocol::Blocks => "blocks",
+		}
+	}
+}
+
+#[cfg(feature = "sixel")]
+fn sixel_supported() -> bool {
+	viuer::is_sixel_supported()
+}
+
+#[cfg(not(feature = "sixel"))]
+fn sixel_supported() -> bool {
+	false
+}
+
+/// An image drawn in place of the ASCII art. The layout gets blank space
+/// the size of the image, and the image is drawn over it afterwards.
+#[derive(Clone)]
+pub(crate) struct Image {
+	pub path: PathBuf,
+	pub protocol: Protocol,
+	/// The image's size in pixels.
+	pixels: (u32, u32),
+	/// The image's size in cells, once it's been `fit`.
+	columns: u32,
+	rows: u32,
+}
+
+impl Image {
+	/// Reads the image's size. Returns `None` if `protocol` is `Auto` and
+	/// the terminal can't draw images.
+	pub fn new(path: &Path, protocol: Protocol) -> errors::Result<Option<Self>> {
+		let pixels = image::image_dimensions(path).map_err(|e| {
+			errors::FreshfetchError::Io(path.to_string_lossy().into_owned(), e.to_string())
+		})?;
+		Ok(protocol.detect()?.map(|protocol| Image {
+			path: path.to_path_buf(),
+			protocol,
+			pixels,
+			columns: 0,
+			rows: 0,
+		}))
+	}
+
+	/// Sizes the image to `rows` lines, keeping its aspect ratio. `cell` is
+	/// the size of a terminal cell in pixels; cells are assumed to be twice
+	/// as tall as they're wide if it's unknown.
+	pub fn fit(&mut self, rows: u32, cell: Option<(u16, u16)>) {
+		let (cell_width, cell_height) = cell.map_or((1.0, 2.0), |(w, h)| (f64::from(w), f64::from(h)));
+		let (width, height) = (f64::from(self.pixels.0), f64::from(self.pixels.1.max(1)));
+		self.rows = rows.max(1);
+		self.columns = ((f64::from(self.rows) * cell_height * width / height / cell_width).round() as u32).max(1);
+	}
+
+	/// Blank space the size of the image.
+	pub fn placeholder(&self) -> String {
+		vec![" ".repeat(self.columns as usize); self.rows as usize].join("\n")
+	}
+
+	/// What to print to take the image off the screen before it's drawn
+	/// again. Kitty keeps every image it's sent above the text, while the
+	/// other protocols draw into cells that get overwritten anyway.
+	pub fn erase(&self) -> &'static str {
+		if self.protocol == Protocol::Kitty { "\u{1b}_Ga=d\u{1b}\\" } else { "" }
+	}
+
+	/// Draws the image with its top left corner `x` columns right of the
+	/// start of the line `up` lines above the cursor, then puts the cursor
+	/// back.
+	pub fn draw(&self, x: u16, up: u16) -> errors::Result<()> {
+		let config = Config {
+			absolute_offset: false,
+			x,
+			y: -(up.min(i16::MAX as u16) as i16),
+			restore_cursor: true,
+			width: Some(self.columns),
+			height: Some(self.rows),
+			use_kitty: self.protocol == Protocol::Kitty,
+			use_iterm: self.protocol == Protocol::Iterm,
+			#[cfg(feature = "sixel")]
+			use_sixel: self.protocol == Protocol::Sixel,
+			..Default::default()
+		};
+		viuer::print_from_file(&self.path, &config).map_err(|e| {
+			errors::FreshfetchError::General(format!("Failed to draw \"{}\": {}", self.path.display(), e))
+		})?;
+		Ok(())
+	}
+}
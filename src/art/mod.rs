@@ -0,0 +1,275 @@
+use crate::mlua;
+
+use crate::assets::ascii_art;
+use crate::color;
+use crate::errors;
+use crate::info;
+use crate::assets;
+use crate::utils;
+use info::distro;
+
+pub(crate) mod clml;
+pub(crate) mod effect;
+pub(crate) mod image;
+pub(crate) mod pack;
+pub(crate) mod picture;
+
+use std::fs;
+use std::path::{ Path };
+
+use mlua::prelude::*;
+
+use crate::{ Inject, Arguments };
+use info::{ Info };
+use distro::{ DistroColors };
+use assets::{ ANSI, PRINT };
+use pack::{ Pack };
+use self::image::{ Image, Protocol };
+use picture::{ Style };
+use effect::{ Effect };
+use color::{ Color };
+
+#[derive(Clone)]
+pub(crate) struct Art {
+	inner: String,
+	width: i32,
+	height: i32,
+	logo: bool,
+	/// Where the art came from, for `--verbose`.
+	source: String,
+	/// The art's `_small` variant, for when the terminal is too narrow.
+	small: Option<String>,
+	/// An image drawn in the art's place.
+	image: Option<Image>,
+}
+
+impl Art {
+	pub fn new(info: &mut Info, arguments: &Arguments) -> errors::Result<Self> {
+		let mut to_return = Art {
+			inner: String::new(),
+			width: 0,
+			height: 0,
+			logo: false,
+			source: String::new(),
+			small: None,
+			image: None,
+		};
+
+		// Get inner & distro colors.
+		{
+			match arguments.ascii_distro.clone() {
+				None => {
+					if let Some(art) = arguments.paths.art() {
+						let file = fs::read_to_string(&art).map_err(|e| {
+                            errors::FreshfetchError::Io(art.to_string_lossy().into_owned(), e.to_string())
+                        })?;
+                        
+                        to_return.inner = {
+                            let mut ctx = Lua::new();
+                            utils::dimensions::inject(&mut ctx)?;
+                            picture::inject(&mut ctx)?;
+                            ctx.load(PRINT).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+                            ctx.load(ANSI).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+                            ctx.load(&file).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+                            
+                            let result = ctx.globals().get::<&str, String>("__freshfetch__").map_err(|e| {
+                                errors::FreshfetchError::Lua(e.to_string())
+                            })?;
+                            result
+                        };
+                        to_return.source = format!("{}", art.display());
+					} else {
+						let packs = pack::load(&arguments.paths.art_packs())?;
+						let distro = &info.distro;
+						let id = distro.id.as_deref();
+						let got = ascii_art::select(id, &distro.id_like, &distro.short_name);
+						match (pack::select(&packs, id, &distro.id_like, &distro.short_name), &got.rule, distro.ansi_color) {
+							(Some((pack, rule)), _, _) => {
+								to_return.source = format!("{} from {}, since {}", pack.name, pack.path.display(), rule);
+								to_return.use_pack(info, pack);
+								to_return.small = small_variant(&packs, &pack.name, false);
+							}
+							// No art for this distro, so Tux is drawn as usual,
+							// with its os-release color as his first color.
+							(None, ascii_art::Rule::Fallback, Some(tint)) => {
+								let (art, mut colors) = ascii_art::get_cli("linux");
+								colors[0] = Some(tint);
+								to_return.inner = String::from(art);
+								to_return.source = String::from("linux, since nothing matched, with os-release ANSI_COLOR as its first color");
+								to_return.small = small_variant(&packs, "linux", true);
+								info.distro.colors = DistroColors::from(colors);
+							}
+							_ => {
+								to_return.inner = String::from(got.art);
+								to_return.source = format!("{}, since {}", got.name, got.rule);
+								to_return.small = small_variant(&packs, got.name, true);
+								info.distro.colors = DistroColors::from(got.colors);
+							}
+						}
+					}
+				}
+				Some(a) => {
+					// Art packs can replace bundled art of the same name.
+					let packs = pack::load(&arguments.paths.art_packs())?;
+					if let Some(pack) = packs.iter().find(|v| v.is_named(&a)) {
+						to_return.source = format!("{} from {}, from --ascii_distro", pack.name, pack.path.display());
+						to_return.use_pack(info, pack);
+						to_return.small = small_variant(&packs, &pack.name, false);
+					} else if !ascii_art::exists(&a) {
+						let mut names: Vec<&str> = packs.iter()
+							.flat_map(|v| std::iter::once(&v.name).chain(v.aliases.iter()))
+							.map(String::as_str)
+							.collect();
+						for name in ascii_art::names() { names.push(name); }
+						let suggestions = ascii_art::suggest(&a, names.into_iter());
+						return Err(errors::FreshfetchError::General(if suggestions.is_empty() {
+							format!("Unknown ASCII art \"{}\". Run `freshfetch --list-ascii` to see every option.", a)
+						} else {
+							format!("Unknown ASCII art \"{}\". Did you mean: {}?", a, suggestions.join(", "))
+						}));
+					} else {
+						let got = ascii_art::get(&a);
+						to_return.inner = String::from(got.0);
+						to_return.source = format!("{}, from --ascii_distro", a);
+						to_return.small = small_variant(&packs, &a, true);
+						info.distro.colors = DistroColors::from(got.1);
+					}
+				}
+			}
+		}
+
+		to_return.measure();
+
+		to_return.logo = arguments.logo;
+
+		Ok(to_return)
+	}
+}
+
+impl Art {
+	fn use_pack(&mut self, info: &mut Info, pack: &Pack) {
+		self.inner = pack.art.clone();
+		info.distro.colors = DistroColors::from(pack.colors);
+	}
+
+	fn measure(&mut self) {
+		let (w, h) = crate::utils::get_dimensions(&self.inner);
+		self.width = w;
+		self.height = h;
+	}
+
+	pub fn width(&self) -> i32 {
+		self.width
+	}
+
+	/// Whether only the art is drawn, for `--logo`.
+	pub fn is_logo(&self) -> bool {
+		self.logo
+	}
+
+	/// Whether there's any art left to draw.
+	pub fn is_hidden(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Switches to the art's `_small` variant, if it has one. The info keeps
+	/// the large art's colors.
+	pub fn shrink(&mut self) {
+		if let Some(small) = self.small.take() {
+			self.inner = small;
+			self.source.push_str(", then its _small variant to fit the terminal");
+			self.measure();
+		}
+	}
+
+	/// Drops the art, leaving just the info.
+	pub fn hide(&mut self) {
+		self.inner.clear();
+		self.small = None;
+		self.image = None;
+		self.source.push_str(", then dropped to fit the terminal");
+		self.width = 0;
+		self.height = 0;
+	}
+
+	/// Draws the picture at `path` as text instead of the art. The info keeps
+	/// the distro's colors.
+	pub fn use_picture(&mut self, path: &Path, width: u32, style: Style) -> errors::Result<()> {
+		self.inner = picture::render(path, width, style)?;
+		self.source = format!("{} as text, instead of {}", path.display(), self.source);
+		self.small = None;
+		self.measure();
+		Ok(())
+	}
+
+	/// Recolors the art, and its `_small` variant, with `effect`. `palette`
+	/// is the distro's colors.
+	pub fn apply_effect(&mut self, effect: &Effect, palette: &[Color]) {
+		self.inner = effect.apply(&self.inner, palette);
+		self.small = self.small.take().map(|v| effect.apply(&v, palette));
+		self.source.push_str(", recolored with --art-effect");
+		self.measure();
+	}
+
+	/// Draws the image at `path` instead of the art, if the terminal can.
+	/// Otherwise, the art stays.
+	pub fn use_image(&mut self, path: &Path, protocol: Protocol) -> errors::Result<()> {
+		match Image::new(path, protocol)? {
+			Some(image) => {
+				self.source = format!("{}, drawn with {}, instead of {}", path.display(), image.protocol.name(), self.source);
+				self.small = None;
+				self.image = Some(image);
+			}
+			None => self.source.push_str(", since the terminal can't draw images"),
+		}
+		Ok(())
+	}
+
+	/// Sizes the image, if there is one, to `rows` lines, and leaves blank
+	/// space for it as the art.
+	pub fn fit_image(&mut self, rows: u32, cell: Option<(u16, u16)>) {
+		if let Some(image) = &mut self.image {
+			image.fit(rows, cell);
+			self.inner = image.placeholder();
+			self.measure();
+		}
+	}
+
+	pub fn image(&self) -> Option<&Image> {
+		self.image.as_ref()
+	}
+
+	/// The art, with its colors.
+	pub fn text(&self) -> &str {
+		&self.inner
+	}
+
+	/// Which art this is, and why it was picked.
+	pub fn source(&self) -> &str {
+		&self.source
+	}
+}
+
+/// The `_small` variant of the art called `name`. Art packs can provide one,
+/// like they can any other art, and bundled art falls back to its own.
+fn small_variant(packs: &[Pack], name: &str, bundled: bool) -> Option<String> {
+	if name.ends_with("_small") { return None; }
+	let small = format!("{}_small", name);
+	match packs.iter().find(|v| v.is_named(&small)) {
+		Some(pack) => Some(pack.art.clone()),
+		None if bundled => ascii_art::small(name).map(|v| String::from(v.0)),
+		None => None,
+	}
+}
+
+impl Inject for Art {
+	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
+		let globals = lua.globals();
+
+		globals.set("art", self.inner.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		globals.set("artWidth", self.width).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		globals.set("artHeight", self.height).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		globals.set("logo", self.logo).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        Ok(())
+	}
+}
@@ -0,0 +1,156 @@
+use crate::assets::ascii_art;
+use crate::color;
+use crate::errors;
+
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use super::clml;
+use ascii_art::{ Rule };
+use ascii_art::meta::{ self, Meta, Rules };
+use color::{ Color };
+
+/// An ASCII art from an art pack: a `*.clml` or `*.txt` file in an `ascii`
+/// directory, loaded at runtime.
+///
+/// Files may start with the same front-matter as the bundled art, `Meta`.
+/// `.clml` files use the bundled art's color tags, like `<blue>`. Both kinds
+/// can use neofetch's `${c1}` to `${c6}`.
+#[derive(Clone, Debug)]
+pub(crate) struct Pack {
+	pub name: String,
+	pub aliases: Vec<String>,
+	pub path: PathBuf,
+	/// The art, with its colors.
+	pub art: String,
+	pub colors: [Option<Color>; 4],
+	rules: Rules,
+}
+
+impl Pack {
+	fn parse(path: &Path, content: &str) -> Result<Self, String> {
+		let (front_matter, body): (Meta<Color>, &str) = meta::parse(content)?;
+		if front_matter.colors.len() > 6 {
+			return Err(String::from("Expected at most 6 colors, for ${c1} to ${c6}."));
+		}
+		let name = match front_matter.name {
+			Some(v) => v,
+			None => path.file_stem()
+				.map(|v| v.to_string_lossy().into_owned())
+				.ok_or_else(|| String::from("The file has no name."))?,
+		};
+		let body = body.trim_end_matches(['\r', '\n']);
+		let body = if path.extension().is_some_and(|v| v == "clml") { clml::render(body) } else { String::from(body) };
+		let colors = &front_matter.colors;
+		let color = |i: usize| colors.get(i).or(colors.last()).copied().unwrap_or(Color::indexed(7));
+		let mut art = body;
+		for i in 0..6 {
+			art = art.replace(&format!("${{c{}}}", i + 1), &format!("\u{1b}[0m\u{1b}[1m{}", color(i)));
+		}
+		art.push_str("\u{1b}[0m");
+		Ok(Pack {
+			name,
+			aliases: front_matter.aliases,
+			path: path.to_path_buf(),
+			art,
+			colors: [0, 1, 2, 3].map(|i| colors.get(i).copied()),
+			rules: front_matter.rules,
+		})
+	}
+
+	/// Whether `-a` should pick this art for `name`.
+	pub fn is_named(&self, name: &str) -> bool {
+		self.name == name || self.aliases.iter().any(|v| v == name)
+	}
+
+	/// Human readable descriptions of what picks this art automatically.
+	pub fn matches(&self) -> Vec<String> {
+		let rules = &self.rules;
+		rules.id.iter().map(|v| format!("ID \"{}\"", v))
+			.chain(rules.is.iter().map(|v| format!("is \"{}\"", v)))
+			.chain(rules.starts_with.iter().map(|v| format!("starts with \"{}\"", v)))
+			.chain(rules.contains.iter().map(|v| format!("contains \"{}\"", v)))
+			.collect()
+	}
+
+	fn has_id(&self, id: &str) -> bool {
+		self.rules.id.iter().any(|v| v.eq_ignore_ascii_case(id))
+	}
+
+	fn matches_name(&self, name: &str) -> Option<String> {
+		let lower = name.to_lowercase();
+		let rules = &self.rules;
+		rules.is.iter().find(|v| v.to_lowercase() == lower).map(|v| format!("is \"{}\"", v))
+			.or_else(|| rules.starts_with.iter().find(|v| lower.starts_with(&v.to_lowercase())).map(|v| format!("starts with \"{}\"", v)))
+			.or_else(|| rules.contains.iter().find(|v| lower.contains(&v.to_lowercase())).map(|v| format!("contains \"{}\"", v)))
+	}
+}
+
+/// Loads every art in `dirs`, sorted by file name. When two have the same
+/// name, the one in the earlier directory wins.
+pub(crate) fn load(dirs: &[PathBuf]) -> errors::Result<Vec<Pack>> {
+	let mut to_return: Vec<Pack> = Vec::new();
+	for dir in dirs.iter() {
+		let entries = fs::read_dir(dir).map_err(|e| {
+			errors::FreshfetchError::Io(dir.to_string_lossy().into_owned(), e.to_string())
+		})?;
+		let mut files: Vec<PathBuf> = entries
+			.filter_map(|v| v.ok().map(|v| v.path()))
+			.filter(|v| v.is_file() && v.extension().is_some_and(|v| v == "clml" || v == "txt"))
+			.collect();
+		files.sort();
+		for file in files.iter() {
+			let content = fs::read_to_string(file).map_err(|e| {
+				errors::FreshfetchError::Io(file.to_string_lossy().into_owned(), e.to_string())
+			})?;
+			let pack = Pack::parse(file, &content).map_err(|e| {
+				errors::FreshfetchError::Config(file.to_string_lossy().into_owned(), e)
+			})?;
+			if !to_return.iter().any(|v| v.name == pack.name) {
+				to_return.push(pack);
+			}
+		}
+	}
+	Ok(to_return)
+}
+
+/// Picks an art pack entry for a distro, the same way `ascii_art::select`
+/// does: by `ID`, then `ID_LIKE`, then name.
+pub(crate) fn select<'a>(packs: &'a [Pack], id: Option<&str>, id_like: &[String], name: &str) -> Option<(&'a Pack, Rule)> {
+	if let Some(id) = id {
+		if let Some(pack) = packs.iter().find(|v| v.has_id(id)) {
+			return Some((pack, Rule::Id(String::from(id))));
+		}
+	}
+	for parent in id_like.iter() {
+		if let Some(pack) = packs.iter().find(|v| v.has_id(parent)) {
+			return Some((pack, Rule::IdLike(parent.clone())));
+		}
+	}
+	packs.iter().find_map(|v| v.matches_name(name).map(|rule| (v, Rule::Name(rule))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_neofetch_art_with_front_matter() {
+		let content = "+++\nname = \"mine\"\ncolors = [4, \"bright_white\"]\n[match]\nid = [\"mydistro\"]\n+++\n${c1}/\\\\${c2}_\n";
+		let pack = Pack::parse(Path::new("ascii/x.txt"), content).unwrap();
+		assert_eq!(pack.name, "mine");
+		assert_eq!(pack.art, "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;4m/\\\\\u{1b}[0m\u{1b}[1m\u{1b}[38;5;15m_\u{1b}[0m");
+		assert_eq!(pack.colors, [Some(Color::indexed(4)), Some(Color::indexed(15)), None, None]);
+		let packs = [pack];
+		let (_, rule) = select(&packs, Some("other"), &[String::from("mydistro")], "").unwrap();
+		assert_eq!(rule, Rule::IdLike(String::from("mydistro")));
+	}
+
+	#[test]
+	fn renders_clml_tags() {
+		let pack = Pack::parse(Path::new("y.clml"), "<blue>#<255-bg 8><>\n").unwrap();
+		assert_eq!(pack.name, "y");
+		assert_eq!(pack.art, "\u{1b}[34m#\u{1b}[48;5;8m<>\u{1b}[0m");
+		assert!(Pack::parse(Path::new("z.txt"), "+++\nname = 1\n").is_err());
+	}
+}
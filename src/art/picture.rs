@@ -0,0 +1,162 @@
+use crate::image;
+use crate::mlua;
+
+use crate::errors;
+
+use std::fmt::Write;
+use std::path::{ Path, PathBuf };
+
+use image::{ Rgba, RgbaImage };
+use image::imageops::{ FilterType };
+use mlua::prelude::*;
+use serde::Deserialize;
+
+/// How a picture is drawn as text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Style {
+	/// `▀` and `▄`, two pixels to a cell, each with its own color.
+	#[default]
+	Blocks,
+	/// Braille dots, eight to a cell, one color per cell. Finer, but only
+	/// the shape survives, not the shading.
+	Braille,
+}
+
+impl Style {
+	pub fn parse(s: &str) -> Result<Self, String> {
+		match s.to_lowercase().as_str() {
+			"blocks" => Ok(Style::Blocks),
+			"braille" => Ok(Style::Braille),
+			_ => Err(format!("Unknown style \"{}\". Expected one of: blocks, braille.", s)),
+		}
+	}
+
+	/// The pixels a cell is made of, across and down.
+	fn cell(&self) -> (u32, u32) {
+		match self {
+			Style::Blocks => (1, 2),
+			Style::Braille => (2, 4),
+		}
+	}
+}
+
+/// Pixels less opaque than this are left blank.
+const OPAQUE: u8 = 128;
+
+/// Renders the picture at `path` as colored text, `width` columns wide.
+/// Colors are truecolor, and are downsampled with the rest of the output.
+pub(crate) fn render(path: &Path, width: u32, style: Style) -> errors::Result<String> {
+	let picture = image::open(path).map_err(|e| {
+		errors::FreshfetchError::Io(path.to_string_lossy().into_owned(), e.to_string())
+	})?;
+	let (cell_width, cell_height) = style.cell();
+	let width = width.max(1);
+	// Cells are about twice as tall as they're wide, which both styles'
+	// pixels already account for, so pixels stay square.
+	let rows = ((f64::from(width) * f64::from(picture.height()) / f64::from(picture.width().max(1))) / 2.0).round().max(1.0) as u32;
+	let pixels = picture.resize_exact(width * cell_width, rows * cell_height, FilterType::Triangle).to_rgba8();
+	Ok(match style {
+		Style::Blocks => blocks(&pixels),
+		Style::Braille => braille(&pixels),
+	})
+}
+
+fn fg(Rgba([r, g, b, _]): Rgba<u8>) -> String {
+	format!("\u{1b}[38;2;{};{};{}m", r, g, b)
+}
+
+fn bg(Rgba([r, g, b, _]): Rgba<u8>) -> String {
+	format!("\u{1b}[48;2;{};{};{}m", r, g, b)
+}
+
+fn blocks(pixels: &RgbaImage) -> String {
+	let mut lines = Vec::new();
+	for y in (0..pixels.height()).step_by(2) {
+		let mut line = String::new();
+		for x in 0..pixels.width() {
+			let top = *pixels.get_pixel(x, y);
+			let bottom = *pixels.get_pixel(x, y + 1);
+			match (top[3] >= OPAQUE, bottom[3] >= OPAQUE) {
+				(true, true) => { let _ = write!(line, "{}{}▀\u{1b}[0m", fg(top), bg(bottom)); }
+				(true, false) => { let _ = write!(line, "{}▀\u{1b}[0m", fg(top)); }
+				(false, true) => { let _ = write!(line, "{}▄\u{1b}[0m", fg(bottom)); }
+				(false, false) => line.push(' '),
+			}
+		}
+		lines.push(line);
+	}
+	lines.join("\n")
+}
+
+/// The bit each dot of a 2x4 cell sets in a braille character, by row.
+static DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+fn braille(pixels: &RgbaImage) -> String {
+	let luma = |Rgba([r, g, b, _]): Rgba<u8>| 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+	// Dots are lit where the picture is brighter than average, so both dark
+	// and light pictures keep their shape.
+	let opaque: Vec<f64> = pixels.pixels().filter(|p| p[3] >= OPAQUE).map(|p| luma(*p)).collect();
+	let threshold = opaque.iter().sum::<f64>() / opaque.len().max(1) as f64;
+	let mut lines = Vec::new();
+	for y in (0..pixels.height()).step_by(4) {
+		let mut line = String::new();
+		for x in (0..pixels.width()).step_by(2) {
+			let mut bits = 0;
+			let mut sum = [0u32; 3];
+			let mut lit = 0;
+			for (dy, row) in DOTS.iter().enumerate() {
+				for (dx, bit) in row.iter().enumerate() {
+					let pixel = *pixels.get_pixel(x + dx as u32, y + dy as u32);
+					if pixel[3] >= OPAQUE && luma(pixel) >= threshold {
+						bits |= bit;
+						lit += 1;
+						for (i, v) in sum.iter_mut().enumerate() { *v += u32::from(pixel[i]); }
+					}
+				}
+			}
+			match char::from_u32(0x2800 + bits) {
+				Some(c) if lit > 0 => {
+					let color = Rgba([(sum[0] / lit) as u8, (sum[1] / lit) as u8, (sum[2] / lit) as u8, 255]);
+					let _ = write!(line, "{}{}\u{1b}[0m", fg(color), c);
+				}
+				_ => line.push(' '),
+			}
+		}
+		lines.push(line);
+	}
+	lines.join("\n")
+}
+
+/// Injects `asciiFromImage(path, width, style)` for `art.lua`. `width`
+/// defaults to 32 columns, and `style` to `"blocks"`.
+pub(crate) fn inject(lua: &mut Lua) -> errors::Result<()> {
+	let ascii_from_image = lua.create_function(|_, (path, width, style): (String, Option<u32>, Option<String>)| {
+		let style = match style {
+			Some(v) => Style::parse(&v).map_err(LuaError::RuntimeError)?,
+			None => Style::Blocks,
+		};
+		render(&PathBuf::from(path), width.unwrap_or(32), style).map_err(|e| LuaError::RuntimeError(e.to_string()))
+	}).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	lua.globals().set("asciiFromImage", ascii_from_image).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn renders_blocks_and_braille() {
+		let mut pixels = RgbaImage::new(2, 4);
+		pixels.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+		pixels.put_pixel(0, 1, Rgba([0, 0, 255, 255]));
+		pixels.put_pixel(1, 1, Rgba([0, 255, 0, 255]));
+		assert_eq!(
+			blocks(&pixels),
+			"\u{1b}[38;2;255;0;0m\u{1b}[48;2;0;0;255m▀\u{1b}[0m\u{1b}[38;2;0;255;0m▄\u{1b}[0m\n  ",
+		);
+		// Only green is brighter than the average.
+		assert_eq!(braille(&pixels), "\u{1b}[38;2;0;255;0m⠐\u{1b}[0m");
+	}
+}
@@ -0,0 +1,84 @@
+// This file is shared with `build.rs`, which includes it with `#[path]` to
+// read the bundled art's front-matter, so it can only use crates that are
+// both dependencies and build-dependencies.
+
+use serde::Deserialize;
+
+/// The TOML front-matter of an ASCII art file, between `+++` lines:
+///
+/// ```toml
+/// +++
+/// name = "mydistro"            # Defaults to the file name.
+/// aliases = ["md"]
+/// colors = [4, "bright_white"]
+///
+/// [match]
+/// id = ["mydistro"]
+/// starts_with = ["My Distro"]
+/// +++
+/// ```
+///
+/// `C` is what colors are parsed as.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Meta<C> {
+	/// The name for `-a`.
+	pub name: Option<String>,
+	/// Other names for `-a`.
+	pub aliases: Vec<String>,
+	/// The palette: `${c1}` to `${c6}`, and the info's colors.
+	pub colors: Vec<C>,
+	#[serde(rename = "match")]
+	pub rules: Rules,
+}
+
+impl<C> Default for Meta<C> {
+	fn default() -> Self {
+		Meta {
+			name: None,
+			aliases: Vec::new(),
+			colors: Vec::new(),
+			rules: Rules::default(),
+		}
+	}
+}
+
+/// When an art is picked automatically. Names are compared ignoring case.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+	/// os-release `ID`s, also matched against `ID_LIKE`.
+	pub id: Vec<String>,
+	/// Distro names that must match exactly.
+	pub is: Vec<String>,
+	pub starts_with: Vec<String>,
+	pub contains: Vec<String>,
+}
+
+/// Splits `+++`-delimited front-matter off the start of `content`.
+pub fn split(content: &str) -> Result<(Option<&str>, &str), String> {
+	let mut lines = content.split_inclusive('\n');
+	match lines.next() {
+		Some(first) if first.trim_end() == "+++" => (),
+		_ => return Ok((None, content)),
+	}
+	let start = content.find('\n').map_or(content.len(), |i| i + 1);
+	let mut end = start;
+	for line in lines {
+		if line.trim_end() == "+++" {
+			return Ok((Some(&content[start..end]), &content[end + line.len()..]));
+		}
+		end += line.len();
+	}
+	Err(String::from("The front-matter has no closing \"+++\"."))
+}
+
+/// Parses the front-matter of `content`, and returns it with the art.
+pub fn parse<C: serde::de::DeserializeOwned>(content: &str) -> Result<(Meta<C>, &str), String> {
+	let (front_matter, body) = split(content)?;
+	let meta = match front_matter {
+		Some(v) => toml::from_str(v).map_err(|e| e.to_string())?,
+		None => Meta::default(),
+	};
+	Ok((meta, body))
+}
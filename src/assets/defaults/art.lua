@@ -0,0 +1,12 @@
+-- Custom ASCII art.
+--
+-- Rename this file to art.lua to use it. Whatever it prints replaces the
+-- distro's ASCII art, however it would have been picked. The color
+-- helpers from ansi.lua, such as `blue()`, `bold()` and `reset()`, are
+-- available here too.
+
+print(""..blue().."      /\\      "..reset())
+print(""..blue().."     /  \\     "..reset())
+print(""..blue().."    / /\\ \\    "..reset())
+print(""..cyan().."   / ____ \\   "..reset())
+print(""..cyan().."  /_/    \\_\\  "..reset())
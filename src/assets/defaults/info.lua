@@ -1,391 +1,366 @@
 function round(x)
-    return x + 0.5 - (x + 0.5) % 1
+	return x + 0.5 - (x + 0.5) % 1
 end
 
--- user@host
-if context ~= nil then
-	print(""
+-- Returns the configured color `name`, or `fallback` if it isn't set.
+local function color(name, fallback)
+	if config.colors[name] ~= nil then
+		return config.colors[name]
+	end
+	return fallback
+end
+
+-- Returns the configured label for `module`, or `fallback` if it isn't set.
+local function label(module, fallback)
+	if config.labels[module] ~= nil then
+		return config.labels[module]
+	end
+	return fallback
+end
+
+-- Prints a `key: value` line for `module`.
+local function line(module, key, value)
+	local output = ""
 		..bold()
-		..distroColors[1]
-		..context.user
+		..color("key", distroColors[2])
+		..label(module, key)
 		..reset()
-		..bold()
-		.."@"
-		..distroColors[2]
-		..context.host
-		..reset())
+		..config.separator
+	if config.colors.value ~= nil then
+		output = output..config.colors.value..value..reset()
+	else
+		output = output..value
+	end
+	print(output)
+end
+
+local modules = {}
+
+-- user@host
+modules.context = function()
+	if context ~= nil then
+		print(""
+			..bold()
+			..color("title", distroColors[1])
+			..context.user
+			..reset()
+			..bold()
+			.."@"
+			..color("title", distroColors[2])
+			..context.host
+			..reset())
+	end
 end
 
 -- OS
-if distro ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."OS"
-		..reset()
-		..": "
-		..distro.short_name
-		.." "
-		..distro.architecture)
+modules.os = function()
+	if distro ~= nil then
+		line("os", "OS", distro.short_name.." "..distro.architecture)
+	end
 end
 
 -- Host
-if host ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Host"
-		..reset()
-		..": "
-		..host.model)
+modules.host = function()
+	if host ~= nil then
+		line("host", "Host", host.model)
+	end
 end
 
 -- Kernel
-if kernel ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Kernel"
-		..reset()
-		..": "
-		..kernel.name
-		.." "
-		..kernel.version)
+modules.kernel = function()
+	if kernel ~= nil then
+		line("kernel", "Kernel", kernel.name.." "..kernel.version)
+	end
 end
 
 -- Uptime
-if uptime ~= nil then
-	local output = ""
-	local function comma()
-		if output ~= "" then output = output..", " end
-	end
-	local function s(v)
-		if v ~= 1 and v ~= 0 then
-			return "s"
-		else
-			return ""
+modules.uptime = function()
+	if uptime ~= nil then
+		local output = ""
+		local function comma()
+			if output ~= "" then output = output..", " end
 		end
+		local function s(v)
+			if v ~= 1 and v ~= 0 then
+				return "s"
+			else
+				return ""
+			end
+		end
+		if uptime.days >= 1 then
+			output = output..uptime.days.." day"..s(uptime.days)
+		end
+		if uptime.hours >= 1 then
+			comma()
+			output = output..uptime.hours.." hour"..s(uptime.hours)
+		end
+		if uptime.minutes >= 1 then
+			comma()
+			output = output..uptime.minutes.." minute"..s(uptime.minutes)
+		elseif uptime.hours == 0 then
+			comma()
+			output = output..uptime.seconds.." second"..s(uptime.seconds)
+		end
+		line("uptime", "Uptime", output)
 	end
-	if uptime.days >= 1 then
-		output = output..uptime.days.." day"..s(uptime.days)
-	end
-	if uptime.hours >= 1 then
-		comma()
-		output = output..uptime.hours.." hour"..s(uptime.hours)
-	end
-	if uptime.minutes >= 1 then
-		comma()
-		output = output..uptime.minutes.." minute"..s(uptime.minutes)
-	elseif uptime.hours == 0 then
-		comma()
-		output = output..uptime.seconds.." second"..s(uptime.seconds)
-	end
-
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Uptime"
-		..reset()
-		..": "
-		..output)
 end
 
 -- Packages
-if packageManagers ~= nil then
-	local output = ""
-	if #packageManagers ~= 0 then
-		for i,packageManager in pairs(packageManagers) do
-			if packageManager.packages == 0 then
-				table.remove(packageManagers, i)
+modules.packages = function()
+	if packageManagers ~= nil then
+		local output = ""
+		if #packageManagers ~= 0 then
+			for i,packageManager in pairs(packageManagers) do
+				if packageManager.packages == 0 then
+					table.remove(packageManagers, i)
+				end
 			end
-		end
-		for i,packageManager in pairs(packageManagers) do
-			if i ~= #packageManagers then
-				output = output
-					..packageManager.packages
-					.." ("
-					..packageManager.name
-					.."), "
-			else
-				output = output
-					..packageManager.packages
-					.." ("
-					..packageManager.name
-					..")"
+			for i,packageManager in pairs(packageManagers) do
+				if i ~= #packageManagers then
+					output = output
+						..packageManager.packages
+						.." ("
+						..packageManager.name
+						.."), "
+				else
+					output = output
+						..packageManager.packages
+						.." ("
+						..packageManager.name
+						..")"
+				end
 			end
+		else
+			output = "0"
 		end
-	else
-		output = "0"
+		line("packages", "Packages", output)
 	end
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Packages"
-		..reset()
-		..": "
-		..output)
 end
 
 -- Shell
-if shell ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Shell"
-		..reset()
-		..": "
-		..shell.name
-		.." "
-		..shell.version)
+modules.shell = function()
+	if shell ~= nil then
+		line("shell", "Shell", shell.name.." "..shell.version)
+	end
 end
 
 -- Resolution
-if resolution ~= nil then
-	if resolution.refresh ~= nil then
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Resolution"
-			..reset()
-			..": "
-			..resolution.width
-			.."x"
-			..resolution.height
-			.." @ "
-			..round(resolution.refresh)
-			.."Hz")
-	else
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Resolution"
-			..reset()
-			..": "
-			..resolution.width
-			.."x"
-			..resolution.height)
+modules.resolution = function()
+	if resolution ~= nil then
+		if resolution.refresh ~= nil then
+			line("resolution", "Resolution", ""
+				..resolution.width
+				.."x"
+				..resolution.height
+				.." @ "
+				..round(resolution.refresh)
+				.."Hz")
+		else
+			line("resolution", "Resolution", ""
+				..resolution.width
+				.."x"
+				..resolution.height)
+		end
 	end
 end
 
 -- DE
-if de ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."DE"
-		..reset()
-		..": "
-		..de.name
-		.." "
-		..de.version)
+modules.de = function()
+	if de ~= nil then
+		line("de", "DE", de.name.." "..de.version)
+	end
 end
 
 -- WM
-if wm ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."WM"
-		..reset()
-		..": "
-		..wm)
+modules.wm = function()
+	if wm ~= nil then
+		line("wm", "WM", wm)
+	end
 end
 
 -- CPU
-if cpu ~= nil then
-	local freq = (cpu.freq >= 1000)
-		and ""..(cpu.freq / 1000).."GHz"
-		or  ""..cpu.freq.."MHz"
-	print(""
-		..bold()
-		..distroColors[2]
-		.."CPU"
-		..reset()
-		..": "
-		..cpu.name
-		.." ("
-		..cpu.cores
-		..") @ "
-		..freq)
+modules.cpu = function()
+	if cpu ~= nil then
+		local freq = (cpu.freq >= 1000)
+			and ""..(cpu.freq / 1000).."GHz"
+			or  ""..cpu.freq.."MHz"
+		line("cpu", "CPU", ""
+			..cpu.name
+			.." ("
+			..cpu.cores
+			..") @ "
+			..freq)
+	end
 end
 
--- CPU Temp (right after CPU)
-if temperature ~= nil and temperature.cpu ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."CPU Temp"
-		..reset()
-		..": "
-		..round(temperature.cpu)
-		.."°C")
+-- CPU Temp
+modules.cpu_temp = function()
+	if temperature ~= nil and temperature.cpu ~= nil then
+		line("cpu_temp", "CPU Temp", round(temperature.cpu).."°C")
+	end
 end
 
 -- GPU
-if gpus ~= nil then
-	if #gpus ~= 1 then
-		print(""
-			..bold()
-			..distroColors[2]
-			.."GPUs"
-			..reset()
-			..": ")
-		for _,gpu in pairs(gpus) do
-			print(" - "..gpu.brand.." "..gpu.name)
+modules.gpu = function()
+	if gpus ~= nil then
+		if #gpus ~= 1 then
+			line("gpu", "GPUs", "")
+			for _,gpu in pairs(gpus) do
+				print(" - "..gpu.brand.." "..gpu.name)
+			end
+		else
+			line("gpu", "GPU", gpus[1].brand.." "..gpus[1].name)
 		end
-	else
-		print(""
-			..bold()
-			..distroColors[2]
-			.."GPU"
-			..reset()
-			..": "
-			..gpus[1].brand
-			.." "
-			..gpus[1].name)
 	end
 end
 
--- GPU Temp (right after GPU)
-if temperature ~= nil and temperature.gpu ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."GPU Temp"
-		..reset()
-		..": "
-		..round(temperature.gpu)
-		.."°C")
+-- GPU Temp
+modules.gpu_temp = function()
+	if temperature ~= nil and temperature.gpu ~= nil then
+		line("gpu_temp", "GPU Temp", round(temperature.gpu).."°C")
+	end
 end
 
 -- Monitors
-if monitors ~= nil and monitors.count > 0 then
-	if monitors.count == 1 then
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Monitor"
-			..reset()
-			..": "
-			..monitors.monitors[1].name)
-	else
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Monitors"
-			..reset()
-			..": ")
-		for _,monitor in pairs(monitors.monitors) do
-			print(" - "..monitor.name)
+modules.monitors = function()
+	if monitors ~= nil and monitors.count > 0 then
+		if monitors.count == 1 then
+			line("monitors", "Monitor", monitors.monitors[1].name)
+		else
+			line("monitors", "Monitors", "")
+			for _,monitor in pairs(monitors.monitors) do
+				print(" - "..monitor.name)
+			end
 		end
 	end
 end
 
 -- Motherboard
-if motherboard ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Board"
-		..reset()
-		..": "
-		..motherboard.vendor
-		.." "
-		..motherboard.name)
+modules.board = function()
+	if motherboard ~= nil then
+		line("board", "Board", motherboard.vendor.." "..motherboard.name)
+	end
+end
+
+-- CPU usage, and disk and network throughput, measured over
+-- `[info] sample_ms`, or between redraws with --watch.
+local function perSecond(bytes)
+	local units = { "B", "KiB", "MiB", "GiB" }
+	local i = 1
+	while bytes >= 1024 and i < #units do
+		bytes = bytes / 1024
+		i = i + 1
+	end
+	return string.format("%.1f%s/s", bytes, units[i])
+end
+
+modules.cpu_usage = function()
+	if rates ~= nil and rates.cpu ~= nil then
+		line("cpu_usage", "CPU Usage", ""
+			..math.floor(rates.cpu.usage_percent + 0.5)
+			.."% "
+			..sparkline(rates.cpu.history, 100))
+	end
+end
+
+modules.disk_io = function()
+	if rates ~= nil and rates.disks ~= nil then
+		for _,disk in ipairs(rates.disks) do
+			line("disk_io", "Disk I/O ("..disk.name..")", ""
+				.."R "..perSecond(disk.read_bytes_per_second)
+				.." W "..perSecond(disk.write_bytes_per_second)
+				.." "..sparkline(disk.write_history))
+		end
+	end
+end
+
+modules.network_rate = function()
+	if rates ~= nil and rates.network ~= nil then
+		for _,interface in ipairs(rates.network) do
+			line("network_rate", "Traffic ("..interface.interface..")", ""
+				.."↓ "..perSecond(interface.rx_bytes_per_second)
+				.." ↑ "..perSecond(interface.tx_bytes_per_second)
+				.." "..sparkline(interface.rx_history))
+		end
+	end
 end
 
 -- Memory
-if memory ~= nil then
-	-- This memory math is probably inaccurate, but idk how to make it right ;-;
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Memory"
-		..reset()
-		..": "
-		..math.floor(memory.used / 1024)
-		.."MB / "
-		..math.floor(memory.max / 1024)
-		.."MB")
+modules.memory = function()
+	if memory ~= nil then
+		-- This memory math is probably inaccurate, but idk how to make it right ;-;
+		line("memory", "Memory", ""
+			..math.floor(memory.used / 1024)
+			.."MB / "
+			..math.floor(memory.max / 1024)
+			.."MB")
+	end
 end
 
 -- Battery
-if battery ~= nil then
-	local output = battery.capacity.."% ("..battery.status..")"
-	if battery.health ~= nil then
-		output = output.." Health: "..battery.health.."%"
-	end
-	if battery.cycles ~= nil then
-		output = output.." Cycles: "..battery.cycles
+modules.battery = function()
+	if battery ~= nil then
+		local output = battery.capacity.."% ("..battery.status..")"
+		if battery.health ~= nil then
+			output = output.." Health: "..battery.health.."%"
+		end
+		if battery.cycles ~= nil then
+			output = output.." Cycles: "..battery.cycles
+		end
+		line("battery", "Battery", output)
 	end
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Battery"
-		..reset()
-		..": "
-		..output)
 end
 
 -- Disk
-if disk ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Disk ("
-		..disk.mount_point
-		..")"
-		..reset()
-		..": "
-		..disk.used_gb
-		.."GB / "
-		..disk.total_gb
-		.."GB")
+modules.disk = function()
+	if disk ~= nil then
+		line("disk", "Disk ("..disk.mount_point..")", ""
+			..disk.used_gb
+			.."GB / "
+			..disk.total_gb
+			.."GB")
+	end
 end
 
 -- Network
-if network ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Network"
-		..reset()
-		..": "
-		..network.interface
-		.." ("
-		..network.ip
-		..")")
+modules.network = function()
+	if network ~= nil then
+		line("network", "Network", network.interface.." ("..network.ip..")")
+	end
 end
 
 -- Bluetooth
-if bluetooth ~= nil and bluetooth.count > 0 then
+modules.bluetooth = function()
+	if bluetooth ~= nil and bluetooth.count > 0 then
+		line("bluetooth", "Bluetooth", bluetooth.count.." devices")
+	end
+end
+
+-- Color blocks
+modules.colors = function()
+	print("")
 	print(""
-		..bold()
-		..distroColors[2]
-		.."Bluetooth"
-		..reset()
-		..": "
-		..bluetooth.count
-		.." devices")
+		..blackBg()  .."   "
+		..redBg()    .."   "
+		..greenBg()  .."   "
+		..yellowBg() .."   "
+		..blueBg()   .."   "
+		..magentaBg().."   "
+		..cyanBg()   .."   "
+		..whiteBg()  .."   "
+		..reset())
+	print(""
+		..blackBrightBg()  .."   "
+		..redBrightBg()    .."   "
+		..greenBrightBg()  .."   "
+		..yellowBrightBg() .."   "
+		..blueBrightBg()   .."   "
+		..magentaBrightBg().."   "
+		..cyanBrightBg()   .."   "
+		..whiteBrightBg()  .."   "
+		..reset())
+end
+
+for _,name in ipairs(config.modules) do
+	modules[name]()
 end
-print("")
-print(""
-	..blackBg()  .."   "
-	..redBg()    .."   "
-	..greenBg()  .."   "
-	..yellowBg() .."   "
-	..blueBg()   .."   "
-	..magentaBg().."   "
-	..cyanBg()   .."   "
-	..whiteBg()  .."   "
-	..reset())
-print(""
-	..blackBrightBg()  .."   "
-	..redBrightBg()    .."   "
-	..greenBrightBg()  .."   "
-	..yellowBrightBg() .."   "
-	..blueBrightBg()   .."   "
-	..magentaBrightBg().."   "
-	..cyanBrightBg()   .."   "
-	..whiteBrightBg()  .."   "
-	..reset())
@@ -1,22 +1,9 @@
-print(""..save())
-
-print("  "..string.gsub(art, "\n", "\n  "))
-
-print(""..up(artHeight))
-
-if not logo then
-	print(""
-		..right(artWidth + 4)
-		..string.gsub(info,
-			"\n",
-			"\n"..right(artWidth + 4)))
-end
-
-print(""..restore())
-
-if infoHeight > artHeight then
-	print(""..down(infoHeight))
-else
-	print(""..down(artHeight))
-end
-
+-- The built-in layout. compose{} lays out `art` and `info` with the options
+-- in config.toml's [layout], and any of them can be overridden here:
+--
+--   compose{ position = "top", gap = 1, center = true }
+--
+-- It also takes `art`, `info`, `padding` and `width`. Everything it needs is
+-- also available on its own, as `art`, `artWidth`, `artHeight`, `info`,
+-- `infoWidth`, `infoHeight` and `terminal`, for layouts that draw by hand.
+print(compose{})
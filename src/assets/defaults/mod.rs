@@ -1,2 +1,4 @@
 pub(crate) static LAYOUT: &str = include_str!("./layout.lua");
-pub(crate) static INFO: &str = include_str!("./info.lua");
\ No newline at end of file
+pub(crate) static INFO: &str = include_str!("./info.lua");
+pub(crate) static ART: &str = include_str!("./art.lua");
+pub(crate) static CONFIG: &str = include_str!("./config.toml");
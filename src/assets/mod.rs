@@ -3,4 +3,4 @@ pub(crate) mod defaults;
 
 pub(crate) static ANSI: &str = include_str!("./ansi.lua");
 pub(crate) static PRINT: &str = include_str!("./print.lua");
-pub(crate) static HELP: &str = include_str!("./.help.clml");
\ No newline at end of file
+pub(crate) static HELP: &str = include_str!(concat!(env!("OUT_DIR"), "/help.txt"));
\ No newline at end of file
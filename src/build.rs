@@ -1,110 +1,176 @@
 #![allow(dead_code)]
 
 extern crate clml_rs;
+extern crate serde;
+extern crate toml;
 
-//mod assets;
+#[path = "assets/ascii_art/meta.rs"]
+mod meta;
 
+use std::env;
+use std::fmt::Write;
 use std::fs;
 use std::path::{ Path, PathBuf };
 
 use clml_rs::{ clml };
+use serde::Deserialize;
 
-//use assets::ascii_art::{ ASCII_ART };
+use meta::{ Meta };
 
-type BuildList = Vec<(PathBuf, PathBuf)>;
+/// A palette color in the bundled art's front-matter: a 256-color index, or
+/// `"default"` for the terminal's foreground.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Palette {
+	Index(u8),
+	Name(String),
+}
 
-fn get_buildlist(base: &Path) -> BuildList {
-	let mut to_return: BuildList = Vec::new();
-	let buildlist_string = fs::read_to_string(base.join("buildlist")).expect("Failed to read buildlist file!");
-	let buildlist_lines = {
-		let split: Vec<&str> = buildlist_string.split("\n").collect();
-		let mut to_return = Vec::new();
-		for line in split.iter() {
-			if !line.starts_with("#") {
-				to_return.push(*line);
-			}
+impl Palette {
+	fn to_rust(&self, file: &Path) -> String {
+		match self {
+			Palette::Index(n) => format!("Some(Color::indexed({}))", n),
+			Palette::Name(v) if v == "default" || v == "reset" => String::from("Some(Color::DEFAULT)"),
+			Palette::Name(v) => panic!("{}: Unknown color \"{}\". Use a 256-color index or \"default\".", file.display(), v),
+		}
+	}
+}
+
+struct Art {
+	file: PathBuf,
+	name: String,
+	meta: Meta<Palette>,
+	art: String,
+}
+
+/// Every `.clml` file under `dir`.
+fn discover(dir: &Path, out: &mut Vec<PathBuf>) {
+	let entries = fs::read_dir(dir).unwrap_or_else(|e| panic!("Failed to read \"{}\": {}", dir.display(), e));
+	for entry in entries {
+		let path = entry.expect("Failed to read a directory entry").path();
+		if path.is_dir() {
+			discover(&path, out);
+		} else if path.extension().is_some_and(|v| v == "clml") {
+			out.push(path);
 		}
-		to_return
+	}
+}
+
+/// Reads an art file. Its name defaults to the file name, with `_small` or
+/// `_old` added for art in those directories.
+fn read(base: &Path, file: &Path) -> Art {
+	let content = fs::read_to_string(file).unwrap_or_else(|e| panic!("Failed to read \"{}\": {}", file.display(), e));
+	let (meta, body) = meta::parse::<Palette>(&content).unwrap_or_else(|e| panic!("{}: {}", file.display(), e));
+	let stem = file.file_stem().map(|v| v.to_string_lossy().into_owned()).unwrap_or_default();
+	let size = file.strip_prefix(base).ok()
+		.and_then(|v| v.components().next())
+		.map(|v| v.as_os_str().to_string_lossy().into_owned())
+		.unwrap_or_default();
+	let name = meta.name.clone().unwrap_or_else(|| match size.as_str() {
+		"large" => stem.clone(),
+		_ => format!("{}_{}", stem, size),
+	});
+	Art { file: file.to_path_buf(), name, art: clml(body), meta }
+}
+
+/// How specific a name rule is. More specific rules are tried first, so the
+/// order of the files doesn't matter: `is` rules come before `starts_with`
+/// rules, which come before `contains` rules, and within each kind longer
+/// patterns win, so `starts with "ArchBox"` always beats `starts with "Arch"`.
+fn specificity(check: &str, pattern: &str) -> (u8, std::cmp::Reverse<usize>) {
+	let kind = match check {
+		"Is" => 0,
+		"StartsWith" => 1,
+		_ => 2,
 	};
-	for line in buildlist_lines.iter() {
-		let split: Vec<&str> = line.split(" -> ").collect();
-		if split.len() != 2 { panic!("Expected only one ... -> ... statement per line!"); }
-		to_return.push((base.join(split[0]), base.join(split[1])));
+	(kind, std::cmp::Reverse(pattern.len()))
+}
+
+fn generate(arts: &[Art]) -> String {
+	let mut to_return = String::from("// Generated by build.rs from the front-matter of src/assets/ascii_art.\n\n");
+	to_return.push_str("static ARTS: &[Art] = &[\n");
+	for art in arts.iter() {
+		let mut colors: Vec<String> = art.meta.colors.iter().map(|v| v.to_rust(&art.file)).collect();
+		if colors.is_empty() { colors.push(String::from("Some(Color::indexed(7))")); }
+		if colors.len() > 4 { panic!("{}: Expected at most 4 colors.", art.file.display()); }
+		colors.resize(4, String::from("None"));
+		let _ = writeln!(to_return,
+			"\tArt {{ name: {:?}, aliases: &{:?}, ids: &{:?}, art: {:?}, colors: [{}] }},",
+			art.name, art.meta.aliases, art.meta.rules.id, art.art, colors.join(", "));
 	}
+	to_return.push_str("];\n\n");
+
+	let mut rules: Vec<(&str, &str, usize)> = Vec::new();
+	for (i, art) in arts.iter().enumerate() {
+		let meta_rules = &art.meta.rules;
+		rules.extend(meta_rules.is.iter().map(|v| ("Is", v.as_str(), i)));
+		rules.extend(meta_rules.starts_with.iter().map(|v| ("StartsWith", v.as_str(), i)));
+		rules.extend(meta_rules.contains.iter().map(|v| ("Contains", v.as_str(), i)));
+	}
+	rules.sort_by(|a, b| {
+		specificity(a.0, a.1).cmp(&specificity(b.0, b.1))
+			.then_with(|| arts[a.2].name.cmp(&arts[b.2].name))
+	});
+	to_return.push_str("/// Name rules, most specific first.\nstatic RULES: &[(Check, &str, usize)] = &[\n");
+	for (check, pattern, i) in rules.iter() {
+		let _ = writeln!(to_return, "\t(Check::{}, {:?}, {}),", check, pattern, i);
+	}
+	to_return.push_str("];\n");
 	to_return
 }
 
-fn progress(min: usize, max: usize) -> String {
-	let mut bar = String::from(" [");
-	let complete = ((min as f32 / max as f32) * 57.0).floor() as usize;
-	if complete >= 1 {
-		bar = format!("{}{}",
-			bar,
-			String::from("=").repeat(complete - 1));
-	}
-	bar = format!("{}>", bar);
-	let remaining = 57 - complete;
-	if complete >= 1 {
-		bar = format!("{}{}]",
-			bar,
-			String::from(" ").repeat(remaining));
-	} else {
-		bar = format!("{}{}]",
-			bar,
-			String::from(" ").repeat(remaining - 1));
+/// Every name and alias, comma separated and wrapped for the help text.
+fn distro_list(arts: &[Art]) -> String {
+	let mut names: Vec<&str> = arts.iter()
+		.flat_map(|art| std::iter::once(&art.name).chain(art.meta.aliases.iter()))
+		.map(String::as_str)
+		.collect();
+	names.sort();
+	let mut to_return = String::new();
+	let mut width = 0;
+	for (i, name) in names.iter().enumerate() {
+		if i > 0 {
+			to_return.push(',');
+			if width + name.len() + 2 > 72 {
+				to_return.push_str("\n    ");
+				width = 0;
+			} else {
+				to_return.push(' ');
+				width += 2;
+			}
+		}
+		to_return.push_str(name);
+		width += name.len();
 	}
-	format!("{bar} {min:03}/{max:03}: ",
-		bar = bar,
-		min = min,
-		max = max)
+	to_return
 }
 
 fn main() {
-	let base = Path::new("./src/assets/ascii_art/");
-	let buildlist = get_buildlist(base);
-	let len = buildlist.len();
-	for (i, target) in buildlist.iter().enumerate() {
-		println!("\u{001b}[1A\r\u{001b}[K    \u{001b}[1m\u{001b}[36mBuilding\u{001b}[0m{}ASCII art",
-			progress(i, len));
-		let input = fs::read_to_string(&target.0).unwrap_or_else(|_| panic!("Failed to read the file \"{:?}\"!", &target.0));
-		let output = clml(&input);
-		fs::write(&target.1, &output).unwrap_or_else(|_| panic!("Failed to write to the file \"{:?}\"!", &target.1));
-	}
-	println!("\u{001b}[1A\r\u{001b}[K    \u{001b}[1m\u{001b}[32mFinished\u{001b}[0m ASCII art");
+	let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR isn't set"));
+	println!("cargo:rerun-if-changed=src/assets/ascii_art");
+	println!("cargo:rerun-if-changed=src/assets/help.clml");
+
+	let base = Path::new("./src/assets/ascii_art");
+	let mut files = Vec::new();
+	discover(base, &mut files);
+	let mut arts: Vec<Art> = files.iter().map(|v| read(base, v)).collect();
+	arts.sort_by(|a, b| a.name.cmp(&b.name));
 	{
-		let input = fs::read_to_string("./src/assets/help.clml").expect("Failed to read the file \"./src/assets/help.clml\"!");
-		let /*mut*/ output = clml(&input);
-		/*// This is removed because it causes a compile issue-- Once 0.2.0 is released
-          // this will be fixed, because I'll be reworking how the ASCII art is compiled
-          // into the final build.
-         let ascii_distro_list = {
-		let mut output = clml(&input);
-		let ascii_distro_list = {
-			let mut to_use = Vec::new();
-			for art in ASCII_ART.iter() {
-				if art.0 != "" {
-					to_use.push(String::from(art.0));
+		let mut names: Vec<(&str, &Path)> = Vec::new();
+		for art in arts.iter() {
+			for name in std::iter::once(&art.name).chain(art.meta.aliases.iter()) {
+				if let Some((_, other)) = names.iter().find(|(v, _)| *v == name) {
+					panic!("\"{}\" is used by both {} and {}.", name, other.display(), art.file.display());
 				}
+				names.push((name, &art.file));
 			}
-			let mut to_return = String::new();
-			let mut this_line = String::new();
-			for (i, name) in to_use.iter().enumerate() {
-				this_line += name;
-				if i != to_use.len() {
-					this_line += ", "
-				}
-				if this_line.len() >= 64 {
-					to_return += &this_line;
-					to_return += "\n";
-					this_line = String::new();
-				}
-			}
-			to_return += &this_line;
-			to_return += "\n";
-			to_return = to_return.replace("\n", &(String::from("\n") + &" ".repeat(4)));
-			to_return
-		};
-		output = output.replace("ASCII_DISTRO_LIST", &ascii_distro_list);*/
-		fs::write("./src/assets/.help.clml", output).expect("Failed to write to the file \"./src/assets/.help.clml\"!");
+		}
 	}
+	let path = out_dir.join("ascii_art.rs");
+	fs::write(&path, generate(&arts)).unwrap_or_else(|e| panic!("Failed to write \"{}\": {}", path.display(), e));
+
+	let input = fs::read_to_string("./src/assets/help.clml").expect("Failed to read the file \"./src/assets/help.clml\"!");
+	let path = out_dir.join("help.txt");
+	let help = clml(&input).replace("ASCII_DISTRO_LIST", &distro_list(&arts));
+	fs::write(&path, help).unwrap_or_else(|e| panic!("Failed to write \"{}\": {}", path.display(), e));
 }
@@ -0,0 +1,260 @@
+use crate::clap;
+use crate::clap_complete;
+
+use crate::art;
+use crate::assets;
+use crate::output;
+
+use std::ffi::{ OsStr };
+use std::path::{ PathBuf };
+
+use clap::{ Arg, ColorChoice, Command };
+use clap::builder::{ PossibleValue, StringValueParser, TypedValueParser };
+use clap_complete::{ Shell };
+
+use art::effect::{ Effect };
+use assets::{ HELP };
+use assets::ascii_art;
+use output::{ Format };
+
+/// The command line definition, shared by argument parsing, shell
+/// completions and the man page.
+pub(crate) fn command() -> Command {
+	Command::new("freshfetch")
+		.version("0.2.0")
+		.author("Jack Johannesen")
+		.about("A fresh take on neofetch.")
+		.override_help(HELP)
+		.arg(
+			Arg::new("ascii_distro")
+				.long("ascii_distro")
+				.short('a')
+				.num_args(1)
+				.value_name("ASCII_DISTRO")
+				.value_parser(AsciiDistroParser)
+				.hide_possible_values(true)
+				.help("Use the ASCII art of ASCII_DISTRO"),
+		)
+		.arg(
+			Arg::new("logo")
+				.long("logo")
+				.short('l')
+				.help("Only output the ASCII art")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("image")
+				.long("image")
+				.num_args(1)
+				.value_name("PATH")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Draw the image at PATH instead of the ASCII art, if the terminal can"),
+		)
+		.arg(
+			Arg::new("ascii_from_image")
+				.long("ascii-from-image")
+				.num_args(1)
+				.value_name("PATH")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Draw the picture at PATH as colored text instead of the ASCII art"),
+		)
+		.arg(
+			Arg::new("art_effect")
+				.long("art-effect")
+				.num_args(1)
+				.value_name("EFFECT")
+				.value_parser(|s: &str| Effect::parse(s))
+				.help("Recolor the ASCII art: gradient:COLORS, vgradient:COLORS, rainbow or palette"),
+		)
+		.arg(
+			Arg::new("verbose")
+				.long("verbose")
+				.short('v')
+				.help("Explain which ASCII art was picked, and why, on stderr")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("color")
+				.long("color")
+				.num_args(1)
+				.value_name("WHEN")
+				.value_parser(clap::value_parser!(ColorChoice))
+				.default_value("auto")
+				.help("Print colors and art: auto (only to a terminal), always or never"),
+		)
+		.arg(
+			Arg::new("width")
+				.long("width")
+				.num_args(1)
+				.value_name("COLUMNS")
+				.value_parser(clap::value_parser!(i32).range(1..))
+				.help("Lay out for a terminal COLUMNS wide instead of detecting it"),
+		)
+		.arg(
+			Arg::new("height")
+				.long("height")
+				.num_args(1)
+				.value_name("LINES")
+				.value_parser(clap::value_parser!(i32).range(1..))
+				.help("Lay out for a terminal LINES tall instead of detecting it"),
+		)
+		.arg(
+			Arg::new("watch")
+				.long("watch")
+				.num_args(0..=1)
+				.value_name("SECS")
+				.value_parser(clap::value_parser!(u64).range(1..))
+				.default_missing_value("2")
+				.conflicts_with_all(["json", "format", "template"])
+				.help("Redraw in place every SECS seconds (2 by default) with fresh memory, uptime, and the like"),
+		)
+		.arg(
+			Arg::new("json")
+				.long("json")
+				.short('j')
+				.help("Output system info as JSON, like --format json")
+				.conflicts_with("format")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("format")
+				.long("format")
+				.short('f')
+				.num_args(1)
+				.value_name("FORMAT")
+				.value_parser(clap::value_parser!(Format))
+				.help("Output system info as json, yaml, toml, env or csv"),
+		)
+		.arg(
+			Arg::new("template")
+				.long("template")
+				.visible_alias("format-string")
+				.num_args(1)
+				.value_name("TEMPLATE")
+				.conflicts_with_all(["json", "format"])
+				.help("Print one line, filling {fields} in TEMPLATE from --format's output"),
+		)
+		.arg(
+			Arg::new("json_schema")
+				.long("json-schema")
+				.help("Print the JSON Schema of --format's output")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("only")
+				.long("only")
+				.num_args(1)
+				.value_delimiter(',')
+				.value_name("MODULES")
+				.help("Only collect and print these modules"),
+		)
+		.arg(
+			Arg::new("exclude")
+				.long("exclude")
+				.num_args(1)
+				.value_delimiter(',')
+				.value_name("MODULES")
+				.help("Don't collect or print these modules"),
+		)
+		.arg(
+			Arg::new("config_dir")
+				.long("config-dir")
+				.num_args(1)
+				.value_name("DIR")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Read config.toml and Lua files from DIR"),
+		)
+		.arg(
+			Arg::new("profile")
+				.long("profile")
+				.short('p')
+				.num_args(1)
+				.value_name("NAME")
+				.help("Use the profile in <config dir>/profiles/NAME"),
+		)
+		.arg(
+			Arg::new("layout")
+				.long("layout")
+				.num_args(1)
+				.value_name("FILE")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Use FILE as layout.lua"),
+		)
+		.arg(
+			Arg::new("info")
+				.long("info")
+				.num_args(1)
+				.value_name("FILE")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Use FILE as info.lua"),
+		)
+		.arg(
+			Arg::new("art")
+				.long("art")
+				.num_args(1)
+				.value_name("FILE")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Use FILE as art.lua"),
+		)
+		.arg(
+			Arg::new("dump_defaults")
+				.long("dump-defaults")
+				.num_args(0..=1)
+				.value_name("DIR")
+				.value_parser(clap::value_parser!(PathBuf))
+				.help("Write the built-in Lua files and a sample config to DIR (defaults to the profile's directory, or the config directory)"),
+		)
+		.arg(
+			Arg::new("force")
+				.long("force")
+				.requires("dump_defaults")
+				.action(clap::ArgAction::SetTrue)
+				.help("Overwrite existing files with --dump-defaults"),
+		)
+		.arg(
+			Arg::new("list_ascii")
+				.long("list-ascii")
+				.help("List every bundled ASCII art")
+				.action(clap::ArgAction::SetTrue),
+		)
+		.arg(
+			Arg::new("preview_ascii")
+				.long("preview-ascii")
+				.num_args(0..=1)
+				.value_name("PATTERN")
+				.help("Print every bundled ASCII art whose name contains PATTERN"),
+		)
+		.arg(
+			Arg::new("man")
+				.long("man")
+				.action(clap::ArgAction::SetTrue)
+				.help("Print a man page"),
+		)
+		.subcommand(
+			Command::new("completions")
+				.about("Print a shell completion script")
+				.arg(
+					Arg::new("shell")
+						.required(true)
+						.value_name("SHELL")
+						.value_parser(clap::value_parser!(Shell)),
+				),
+		)
+}
+
+/// Accepts any string, like a plain `String` argument, but offers every
+/// bundled ASCII art name to shell completions.
+#[derive(Clone)]
+struct AsciiDistroParser;
+
+impl TypedValueParser for AsciiDistroParser {
+	type Value = String;
+
+	fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<String, clap::Error> {
+		StringValueParser::new().parse_ref(cmd, arg, value)
+	}
+
+	fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+		Some(Box::new(ascii_art::names().map(PossibleValue::new)))
+	}
+}
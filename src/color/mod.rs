@@ -0,0 +1,75 @@
+use crate::clap;
+
+pub(crate) mod sgr;
+pub(crate) mod value;
+
+use std::env;
+use std::io::{ self, IsTerminal };
+
+use clap::{ ColorChoice };
+
+pub(crate) use sgr::{ downsample };
+pub(crate) use value::{ Color };
+
+/// How many colors the output can use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Depth {
+	None,
+	Ansi16,
+	Ansi256,
+	TrueColor,
+}
+
+/// How to print the layout.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ColorMode {
+	/// Print only the info, without colors, art or cursor movement. Used
+	/// when stdout isn't a terminal and colors weren't forced.
+	pub plain: bool,
+	/// Colors deeper than this are downsampled in the final output.
+	pub depth: Depth,
+}
+
+impl ColorMode {
+	/// Works out the mode from `--color`, then `NO_COLOR`, then
+	/// `CLICOLOR_FORCE`, then whether stdout is a terminal. The depth comes
+	/// from `COLORTERM` and `TERM`.
+	pub fn detect(choice: ColorChoice) -> Self {
+		let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+		let (forced, disabled) = match choice {
+			ColorChoice::Always => (true, false),
+			ColorChoice::Never => (false, true),
+			ColorChoice::Auto => {
+				let no_color = var("NO_COLOR").is_some();
+				(!no_color && var("CLICOLOR_FORCE").is_some_and(|v| v != "0"), no_color)
+			}
+		};
+		let terminal = io::stdout().is_terminal();
+		let depth = if disabled || (!forced && !terminal) {
+			Depth::None
+		} else {
+			let depth = detect_depth(var("COLORTERM").as_deref(), var("TERM").as_deref());
+			// Forcing colors onto a terminal that doesn't claim any still
+			// gets the basic ones.
+			if forced { depth.max(Depth::Ansi16) } else { depth }
+		};
+		ColorMode {
+			plain: !terminal && !forced,
+			depth,
+		}
+	}
+}
+
+fn detect_depth(colorterm: Option<&str>, term: Option<&str>) -> Depth {
+	if let Some("truecolor"|"24bit") = colorterm {
+		return Depth::TrueColor;
+	}
+	match term {
+		None => Depth::Ansi16,
+		Some(term) if term.contains("truecolor") || term.contains("direct") => Depth::TrueColor,
+		Some(term) if term.contains("256color") => Depth::Ansi256,
+		// Serial terminals, and ones that ask for no escapes at all.
+		Some(term) if term == "dumb" || term.starts_with("vt") => Depth::None,
+		Some(_) => Depth::Ansi16,
+	}
+}
@@ -0,0 +1,165 @@
+use crate::utils;
+
+use utils::ansi::{ self, Token };
+
+use super::{ Depth };
+
+/// The 16 basic colors, as xterm draws them.
+static BASIC: [(u8, u8, u8); 16] = [
+	(0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+	(0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+	(127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+	(92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// The levels of each channel in the 6x6x6 color cube.
+static CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Rewrites every SGR sequence in `s` so it only uses colors `depth` can
+/// show. Other attributes, like bold, and other sequences, like cursor
+/// movement, are kept.
+pub(crate) fn downsample(s: &str, depth: Depth) -> String {
+	if depth == Depth::TrueColor {
+		return String::from(s);
+	}
+	let mut to_return = String::with_capacity(s.len());
+	for token in ansi::tokens(s) {
+		match token {
+			Token::Text(v) | Token::Escape(v) => to_return.push_str(v),
+			Token::Csi { raw, params, end } => {
+				if end != 'm' || params.is_empty() {
+					to_return.push_str(raw);
+				} else if let Some(params) = rewrite(params, depth) {
+					to_return.push_str(&format!("\u{1b}[{}m", params));
+				}
+			}
+		}
+	}
+	to_return
+}
+
+/// Rewrites the parameters of one SGR sequence. Returns `None` if nothing is
+/// left, so the sequence can be dropped.
+fn rewrite(params: &str, depth: Depth) -> Option<String> {
+	let params: Vec<&str> = params.split(';').collect();
+	let mut to_return: Vec<String> = Vec::new();
+	let mut i = 0;
+	while i < params.len() {
+		let code = params[i].parse::<u16>().unwrap_or(0);
+		match code {
+			38 | 48 => {
+				let background = code == 48;
+				let color = match params.get(i + 1).copied() {
+					Some("5") => {
+						i += 2;
+						params.get(i).and_then(|v| v.parse::<u8>().ok()).map(Color::Indexed)
+					}
+					Some("2") => {
+						let channel = |n: usize| params.get(i + n).and_then(|v| v.parse::<u8>().ok());
+						let color = match (channel(2), channel(3), channel(4)) {
+							(Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+							_ => None,
+						};
+						i += 4;
+						color
+					}
+					_ => None,
+				};
+				if let Some(color) = color {
+					to_return.extend(color.sgr(background, depth));
+				}
+			}
+			30..=37 | 40..=47 | 90..=97 | 100..=107 => {
+				if depth != Depth::None { to_return.push(String::from(params[i])); }
+			}
+			_ => to_return.push(String::from(params[i])),
+		}
+		i += 1;
+	}
+	if to_return.is_empty() { None } else { Some(to_return.join(";")) }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+	Indexed(u8),
+	Rgb(u8, u8, u8),
+}
+
+impl Color {
+	/// The SGR parameters for this color at `depth`.
+	fn sgr(self, background: bool, depth: Depth) -> Option<String> {
+		let base = if background { 48 } else { 38 };
+		match (self, depth) {
+			(_, Depth::None) => None,
+			(Color::Rgb(r, g, b), Depth::TrueColor) => Some(format!("{};2;{};{};{}", base, r, g, b)),
+			(Color::Indexed(n), Depth::TrueColor | Depth::Ansi256) => Some(format!("{};5;{}", base, n)),
+			(Color::Rgb(r, g, b), Depth::Ansi256) => Some(format!("{};5;{}", base, to_256(r, g, b))),
+			(color, Depth::Ansi16) => {
+				let n = match color {
+					Color::Indexed(n) if n < 16 => n,
+					Color::Indexed(n) => to_16(index_rgb(n)),
+					Color::Rgb(r, g, b) => to_16((r, g, b)),
+				};
+				let offset = if background { 10 } else { 0 };
+				Some(if n < 8 { (30 + offset + n as u16).to_string() } else { (90 + offset + n as u16 - 8).to_string() })
+			}
+		}
+	}
+}
+
+/// The RGB value of a 256-color palette index.
+pub(crate) fn index_rgb(n: u8) -> (u8, u8, u8) {
+	match n {
+		0..=15 => BASIC[n as usize],
+		16..=231 => {
+			let n = n - 16;
+			(CUBE[(n / 36) as usize], CUBE[(n / 6 % 6) as usize], CUBE[(n % 6) as usize])
+		}
+		_ => {
+			let v = 8 + (n - 232) * 10;
+			(v, v, v)
+		}
+	}
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+	let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+	d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// The closest 256-color palette index to an RGB value, from the color cube
+/// or the grayscale ramp.
+fn to_256(r: u8, g: u8, b: u8) -> u8 {
+	let level = |v: u8| (0..6).min_by_key(|i| (CUBE[*i] as i32 - v as i32).abs()).unwrap_or(0) as u8;
+	let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+	let average = (r as u16 + g as u16 + b as u16) / 3;
+	let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+	if distance(index_rgb(gray), (r, g, b)) < distance(index_rgb(cube), (r, g, b)) { gray } else { cube }
+}
+
+/// The closest of the 16 basic colors to an RGB value.
+fn to_16(rgb: (u8, u8, u8)) -> u8 {
+	(0..16).min_by_key(|i| distance(BASIC[*i as usize], rgb)).unwrap_or(7)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn downsamples() {
+		let s = "\u{1b}[1;38;2;255;0;0mred\u{1b}[0m \u{1b}[38;5;208m\u{1b}[2A";
+		assert_eq!(downsample(s, Depth::TrueColor), s);
+		assert_eq!(downsample(s, Depth::Ansi256), "\u{1b}[1;38;5;196mred\u{1b}[0m \u{1b}[38;5;208m\u{1b}[2A");
+		assert_eq!(downsample(s, Depth::Ansi16), "\u{1b}[1;91mred\u{1b}[0m \u{1b}[33m\u{1b}[2A");
+		assert_eq!(downsample(s, Depth::None), "\u{1b}[1mred\u{1b}[0m \u{1b}[2A");
+	}
+
+	#[test]
+	fn palette() {
+		assert_eq!(to_256(0, 0, 0), 16);
+		assert_eq!(to_256(128, 128, 128), 244);
+		assert_eq!(to_16(index_rgb(4)), 4);
+		assert_eq!(downsample("\u{1b}[48;5;1m", Depth::Ansi16), "\u{1b}[41m");
+	}
+}
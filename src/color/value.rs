@@ -0,0 +1,274 @@
+use crate::mlua;
+
+use crate::errors;
+use crate::utils;
+
+use std::convert::{ TryFrom };
+use std::fmt;
+
+use mlua::prelude::*;
+use serde::{ Deserialize, Serialize, Serializer };
+
+use utils::ansi::{ self, Token };
+
+static NAMES: [&str; 8] = [
+	"black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// What a `Color` is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+	/// The terminal's default foreground.
+	Default,
+	/// A 256-color palette index. 0-15 are the basic colors.
+	Indexed(u8),
+	Rgb(u8, u8, u8),
+}
+
+/// A foreground color, with attributes.
+///
+/// Parsed from names (`red`, `bright_blue`, `reset`), palette indices
+/// (`208`), hex (`#ff8700`, `#f80`) or escape sequences (`\x1b[1;38;5;4m`).
+/// Names can be combined with `bold` and `dim`, as in `bold red`. Config
+/// files can also give a palette index as a bare number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "Source")]
+pub(crate) struct Color {
+	pub kind: Kind,
+	pub bold: bool,
+	pub dim: bool,
+}
+
+impl Color {
+	/// The terminal's default foreground.
+	pub const DEFAULT: Color = Color { kind: Kind::Default, bold: false, dim: false };
+
+	pub const fn indexed(n: u8) -> Self {
+		Color { kind: Kind::Indexed(n), bold: false, dim: false }
+	}
+
+	pub fn parse(s: &str) -> Result<Self, String> {
+		let invalid = || format!("Invalid color \"{}\".", s.escape_default());
+		if s.starts_with('\u{1b}') {
+			return Color::from_escape(s).ok_or_else(invalid);
+		}
+		let mut to_return = Color::DEFAULT;
+		let mut kind = None;
+		for word in s.split(|c: char| c.is_whitespace() || c == '+').filter(|w| !w.is_empty()) {
+			let word = word.to_lowercase();
+			match word.as_str() {
+				"bold" => to_return.bold = true,
+				"dim" => to_return.dim = true,
+				_ if kind.is_some() => return Err(invalid()),
+				"reset" | "default" => kind = Some(Kind::Default),
+				_ => kind = Some(parse_kind(&word).ok_or_else(invalid)?),
+			}
+		}
+		if kind.is_none() && !to_return.bold && !to_return.dim {
+			return Err(invalid());
+		}
+		to_return.kind = kind.unwrap_or(Kind::Default);
+		Ok(to_return)
+	}
+
+	/// Parses a single SGR sequence.
+	fn from_escape(s: &str) -> Option<Self> {
+		let params = match ansi::tokens(s).as_slice() {
+			[Token::Csi { params, end: 'm', .. }] => *params,
+			_ => return None,
+		};
+		let mut to_return = Color::DEFAULT;
+		let params: Vec<u8> = params.split(';')
+			.map(|v| if v.is_empty() { Some(0) } else { v.parse::<u8>().ok() })
+			.collect::<Option<Vec<u8>>>()?;
+		let mut i = 0;
+		while i < params.len() {
+			match params[i] {
+				0 => to_return = Color::DEFAULT,
+				1 => to_return.bold = true,
+				2 => to_return.dim = true,
+				39 => to_return.kind = Kind::Default,
+				n @ 30..=37 => to_return.kind = Kind::Indexed(n - 30),
+				n @ 90..=97 => to_return.kind = Kind::Indexed(n - 90 + 8),
+				38 => match params.get(i + 1) {
+					Some(5) => {
+						to_return.kind = Kind::Indexed(*params.get(i + 2)?);
+						i += 2;
+					}
+					Some(2) => {
+						to_return.kind = Kind::Rgb(*params.get(i + 2)?, *params.get(i + 3)?, *params.get(i + 4)?);
+						i += 4;
+					}
+					_ => return None,
+				},
+				_ => return None,
+			}
+			i += 1;
+		}
+		Some(to_return)
+	}
+
+	/// The escape sequence that switches to this color.
+	pub fn escape(&self) -> String {
+		let mut params = Vec::new();
+		if self.bold { params.push(String::from("1")); }
+		if self.dim { params.push(String::from("2")); }
+		params.push(match self.kind {
+			Kind::Default => String::from("39"),
+			Kind::Indexed(n) => format!("38;5;{}", n),
+			Kind::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+		});
+		format!("\u{1b}[{}m", params.join(";"))
+	}
+
+	/// The color's RGB value, using xterm's values for palette indices. The
+	/// terminal's default foreground has none.
+	pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+		match self.kind {
+			Kind::Default => None,
+			Kind::Indexed(n) => Some(super::sgr::index_rgb(n)),
+			Kind::Rgb(r, g, b) => Some((r, g, b)),
+		}
+	}
+
+	/// The color's RGB value as `#rrggbb`.
+	pub fn hex(&self) -> Option<String> {
+		self.rgb().map(|(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b))
+	}
+
+	/// Serialises the color as `hex`, for `#[serde(serialize_with)]`.
+	pub fn serialize_hex<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.hex().serialize(serializer)
+	}
+
+	/// The name of a basic color, like `bright_red`.
+	pub fn name(&self) -> Option<String> {
+		match self.kind {
+			Kind::Default => Some(String::from("default")),
+			Kind::Indexed(n) if n < 8 => Some(String::from(NAMES[n as usize])),
+			Kind::Indexed(n) if n < 16 => Some(format!("bright_{}", NAMES[n as usize - 8])),
+			_ => None,
+		}
+	}
+
+	/// A table describing the color, for Lua:
+	/// `{ escape, kind, index, rgb = { r, g, b }, hex, name, bold, dim }`.
+	/// `kind` is `"default"`, `"indexed"` or `"rgb"`, and `index`, `rgb`,
+	/// `hex` and `name` are only set when they apply.
+	pub fn describe<'lua>(&self, lua: &'lua Lua) -> errors::Result<LuaTable<'lua>> {
+		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		t.set("escape", self.escape()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		let (kind, rgb) = match self.kind {
+			Kind::Default => ("default", None),
+			Kind::Indexed(n) => {
+				t.set("index", n).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				("indexed", Some(super::sgr::index_rgb(n)))
+			}
+			Kind::Rgb(r, g, b) => ("rgb", Some((r, g, b))),
+		};
+		t.set("kind", kind).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		if let Some((r, g, b)) = rgb {
+			let rgb_t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			rgb_t.set("r", r).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			rgb_t.set("g", g).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			rgb_t.set("b", b).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("rgb", rgb_t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		if let Some(hex) = self.hex() {
+			t.set("hex", hex).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		if let Some(name) = self.name() {
+			t.set("name", name).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		t.set("bold", self.bold).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		t.set("dim", self.dim).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		Ok(t)
+	}
+}
+
+fn parse_kind(word: &str) -> Option<Kind> {
+	if let Some(hex) = word.strip_prefix('#') {
+		if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+			return None;
+		}
+		let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+		return match hex.len() {
+			6 => Some(Kind::Rgb(channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+			3 => {
+				let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+				Some(Kind::Rgb(short(0)?, short(1)?, short(2)?))
+			}
+			_ => None,
+		};
+	}
+	if let Ok(n) = word.parse::<u8>() {
+		return Some(Kind::Indexed(n));
+	}
+	let (name, offset) = match word.strip_prefix("bright_").or_else(|| word.strip_prefix("bright")) {
+		Some(name) => (name, 8),
+		None => (word, 0),
+	};
+	NAMES.iter().position(|n| *n == name).map(|n| Kind::Indexed(n as u8 + offset))
+}
+
+/// What a `Color` can be deserialised from.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Source {
+	Index(u8),
+	Text(String),
+}
+
+impl TryFrom<Source> for Color {
+	type Error = String;
+
+	fn try_from(v: Source) -> Result<Self, String> {
+		match v {
+			Source::Index(n) => Ok(Color::indexed(n)),
+			Source::Text(s) => Color::parse(&s),
+		}
+	}
+}
+
+impl fmt::Display for Color {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.escape())
+	}
+}
+
+/// Colors are serialised as their escape sequence.
+impl Serialize for Color {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.escape())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses() {
+		assert_eq!(Color::parse("red").unwrap(), Color::indexed(1));
+		assert_eq!(Color::parse("bright_blue").unwrap(), Color::indexed(12));
+		assert_eq!(Color::parse("208").unwrap(), Color::indexed(208));
+		assert_eq!(Color::parse("#f80").unwrap().kind, Kind::Rgb(255, 136, 0));
+		assert_eq!(Color::parse("reset").unwrap(), Color::DEFAULT);
+		let bold = Color::parse("bold red").unwrap();
+		assert!(bold.bold && bold.kind == Kind::Indexed(1));
+		assert_eq!(Color::parse("\u{1b}[1;38;5;4m").unwrap(), Color { kind: Kind::Indexed(4), bold: true, dim: false });
+		assert_eq!(Color::parse("\u{1b}[91m").unwrap(), Color::indexed(9));
+		assert!(Color::parse("255 123").is_err());
+		assert!(Color::parse("chartreuse").is_err());
+		assert!(Color::parse("#aébcd").is_err());
+		assert!(Color::parse("#é").is_err());
+	}
+
+	#[test]
+	fn round_trips() {
+		for s in ["red", "bold 208", "dim #102030", "default"] {
+			let color = Color::parse(s).unwrap();
+			assert_eq!(Color::parse(&color.escape()).unwrap(), color);
+		}
+	}
+}
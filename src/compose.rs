@@ -0,0 +1,256 @@
+use crate::mlua;
+
+use crate::errors;
+use crate::utils;
+
+use mlua::prelude::*;
+use serde::Deserialize;
+
+use utils::ansi::{ self, Token };
+
+const RESET: &str = "\u{1b}[0m";
+
+/// Where the art goes, relative to the info.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Position {
+	#[default]
+	Left,
+	Right,
+	Top,
+	Bottom,
+}
+
+impl Position {
+	pub fn parse(s: &str) -> Result<Self, String> {
+		match s.to_lowercase().as_str() {
+			"left" => Ok(Position::Left),
+			"right" => Ok(Position::Right),
+			"top" => Ok(Position::Top),
+			"bottom" => Ok(Position::Bottom),
+			_ => Err(format!("Unknown position \"{}\". Expected one of: left, right, top, bottom.", s)),
+		}
+	}
+
+	/// Whether the art and info are next to each other, rather than stacked.
+	pub fn is_beside(&self) -> bool {
+		matches!(self, Position::Left | Position::Right)
+	}
+}
+
+/// How `compose` lays out the art and info.
+#[derive(Clone, Debug)]
+pub(crate) struct Options {
+	pub position: Position,
+	/// Columns before every line.
+	pub padding: usize,
+	/// Columns between the art and info, or blank lines when they're
+	/// stacked.
+	pub gap: usize,
+	/// Whether to centre the shorter of the two vertically, or the narrower
+	/// of the two horizontally when they're stacked.
+	pub center: bool,
+	/// Lines are cut off at this many columns.
+	pub width: Option<usize>,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Options {
+			position: Position::Left,
+			padding: 2,
+			gap: 2,
+			center: false,
+			width: None,
+		}
+	}
+}
+
+/// A block of lines, like the art or the info.
+struct Block {
+	lines: Vec<String>,
+	width: usize,
+}
+
+impl Block {
+	fn new(s: &str) -> Self {
+		let s = s.trim_end_matches('\n');
+		if s.is_empty() {
+			return Block { lines: Vec::new(), width: 0 };
+		}
+		let lines = carry(s);
+		let width = lines.iter().map(|v| ansi::width(v)).max().unwrap_or(0);
+		Block { lines, width }
+	}
+
+	fn height(&self) -> usize {
+		self.lines.len()
+	}
+
+	fn is_empty(&self) -> bool {
+		self.lines.is_empty()
+	}
+
+	/// Line `row` of a block drawn `offset` rows down.
+	fn line(&self, row: usize, offset: usize) -> Option<&str> {
+		row.checked_sub(offset).and_then(|i| self.lines.get(i)).map(String::as_str)
+	}
+}
+
+/// Splits `s` into lines, starting each one with the SGR sequences still in
+/// effect from the lines before it, so that colors spanning several lines
+/// survive having something else printed beside them.
+fn carry(s: &str) -> Vec<String> {
+	let mut state = String::new();
+	s.split('\n').map(|line| {
+		let to_return = format!("{}{}", state, line);
+		for token in ansi::tokens(line) {
+			if let Token::Csi { raw, params, end: 'm' } = token {
+				if params.is_empty() || params == "0" {
+					state.clear();
+				} else if params.starts_with("0;") {
+					state = String::from(raw);
+				} else {
+					state.push_str(raw);
+				}
+			}
+		}
+		to_return
+	}).collect()
+}
+
+/// `line`, reset so its colors don't leak, then padded to `width` columns.
+fn cell(line: &str, width: usize) -> String {
+	let reset = if line.contains('\u{1b}') { RESET } else { "" };
+	let fill = width.saturating_sub(ansi::width(line));
+	format!("{}{}{}", line, reset, " ".repeat(fill))
+}
+
+fn beside(first: &Block, second: &Block, options: &Options) -> Vec<String> {
+	let height = first.height().max(second.height());
+	let offset = |b: &Block| if options.center { (height - b.height()) / 2 } else { 0 };
+	let (first_offset, second_offset) = (offset(first), offset(second));
+	(0..height).map(|row| {
+		let a = first.line(row, first_offset).unwrap_or("");
+		match second.line(row, second_offset) {
+			Some(b) => format!("{}{}{}", cell(a, first.width), " ".repeat(options.gap), cell(b, 0)),
+			None => cell(a, 0),
+		}
+	}).collect()
+}
+
+fn stacked(first: &Block, second: &Block, options: &Options) -> Vec<String> {
+	let width = first.width.max(second.width);
+	let indent = |b: &Block| " ".repeat(if options.center { (width - b.width) / 2 } else { 0 });
+	let mut to_return: Vec<String> = Vec::with_capacity(first.height() + options.gap + second.height());
+	let first_indent = indent(first);
+	to_return.extend(first.lines.iter().map(|v| format!("{}{}", first_indent, cell(v, 0))));
+	to_return.extend(std::iter::repeat(String::new()).take(options.gap));
+	let second_indent = indent(second);
+	to_return.extend(second.lines.iter().map(|v| format!("{}{}", second_indent, cell(v, 0))));
+	to_return
+}
+
+/// Lays out `art` and `info` as plain lines, without moving the cursor.
+/// Either can be empty, in which case the other is drawn on its own.
+pub(crate) fn compose(art: &str, info: &str, options: &Options) -> String {
+	let art = Block::new(art);
+	let info = Block::new(info);
+	let (first, second) = match options.position {
+		Position::Left | Position::Top => (&art, &info),
+		Position::Right | Position::Bottom => (&info, &art),
+	};
+	let rows = if first.is_empty() || second.is_empty() {
+		let only = if first.is_empty() { second } else { first };
+		only.lines.iter().map(|v| cell(v, 0)).collect()
+	} else if options.position.is_beside() {
+		beside(first, second, options)
+	} else {
+		stacked(first, second, options)
+	};
+	let padding = " ".repeat(options.padding);
+	rows.iter()
+		.map(|row| {
+			let row = if row.is_empty() { String::new() } else { format!("{}{}", padding, row) };
+			match options.width {
+				Some(width) => ansi::truncate(&row, width),
+				None => row,
+			}
+		})
+		.collect::<Vec<String>>()
+		.join("\n")
+}
+
+/// Where `compose` puts the art's top left corner, as a column and a line.
+pub(crate) fn origin(art: &str, info: &str, options: &Options) -> (usize, usize) {
+	let art = Block::new(art);
+	let info = Block::new(info);
+	if info.is_empty() {
+		return (options.padding, 0);
+	}
+	let centered = |size: usize, of: usize| if options.center { (of - size) / 2 } else { 0 };
+	let height = art.height().max(info.height());
+	let width = art.width.max(info.width);
+	match options.position {
+		Position::Left => (options.padding, centered(art.height(), height)),
+		Position::Right => (options.padding + info.width + options.gap, centered(art.height(), height)),
+		Position::Top => (options.padding + centered(art.width, width), 0),
+		Position::Bottom => (options.padding + centered(art.width, width), info.height() + options.gap),
+	}
+}
+
+/// Injects `compose{...}`, which lays out the art and info with `defaults`.
+/// Its table can replace `art`, `info` and any of the options: `position`,
+/// `padding`, `gap`, `center` and `width`.
+pub(crate) fn inject(lua: &mut Lua, art: &str, info: &str, defaults: &Options) -> errors::Result<()> {
+	let (art, info, defaults) = (String::from(art), String::from(info), defaults.clone());
+	let compose_fn = lua.create_function(move |_, t: Option<LuaTable>| {
+		let mut options = defaults.clone();
+		let (mut art, mut info) = (art.clone(), info.clone());
+		if let Some(t) = t {
+			if let Some(v) = t.get::<_, Option<String>>("art")? { art = v; }
+			if let Some(v) = t.get::<_, Option<String>>("info")? { info = v; }
+			if let Some(v) = t.get::<_, Option<String>>("position")? {
+				options.position = Position::parse(&v).map_err(LuaError::RuntimeError)?;
+			}
+			if let Some(v) = t.get::<_, Option<usize>>("padding")? { options.padding = v; }
+			if let Some(v) = t.get::<_, Option<usize>>("gap")? { options.gap = v; }
+			if let Some(v) = t.get::<_, Option<bool>>("center")? { options.center = v; }
+			if let Some(v) = t.get::<_, Option<usize>>("width")? { options.width = Some(v); }
+		}
+		Ok(compose(&art, &info, &options))
+	}).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	lua.globals().set("compose", compose_fn).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn composes_beside() {
+		let options = Options { gap: 1, padding: 1, ..Options::default() };
+		assert_eq!(compose("ab\nc", "1\n2\n3\n", &options), " ab 1\n c  2\n    3");
+		let right = Options { position: Position::Right, center: true, ..options.clone() };
+		assert_eq!(compose("x", "1\n2\n3", &right), " 1\n 2 x\n 3");
+		let narrow = Options { width: Some(4), ..options };
+		assert_eq!(compose("ab", "123", &narrow), " ab ");
+	}
+
+	#[test]
+	fn composes_stacked() {
+		let options = Options { position: Position::Top, padding: 0, gap: 1, center: true, width: None };
+		assert_eq!(compose("/\\", "info", &options), " /\\\n\ninfo");
+		assert_eq!(compose("", "info", &options), "info");
+	}
+
+	#[test]
+	fn carries_colors_across_lines() {
+		let options = Options { padding: 0, gap: 1, ..Options::default() };
+		assert_eq!(
+			compose("\u{1b}[31ma\nb\u{1b}[0m", "1\n2", &options),
+			"\u{1b}[31ma\u{1b}[0m 1\n\u{1b}[31mb\u{1b}[0m\u{1b}[0m 2",
+		);
+	}
+}
@@ -0,0 +1,308 @@
+use crate::mlua;
+use crate::toml;
+
+use crate::art;
+use crate::color;
+use crate::compose;
+use crate::errors;
+use crate::paths;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use mlua::prelude::*;
+use serde::Deserialize;
+use toml::{ Table, Value };
+
+use crate::{ Inject };
+use art::effect::{ Effect };
+use art::image::{ Protocol };
+use art::picture::{ Style };
+use color::{ Color };
+use compose::{ Position };
+use paths::{ Paths };
+
+/// Every module the built-in `info.lua` knows how to print, in the default
+/// order. All but the `SAMPLED` ones are printed by default.
+pub(crate) static MODULES: &[&str] = &[
+	"context",
+	"os",
+	"host",
+	"kernel",
+	"uptime",
+	"packages",
+	"shell",
+	"resolution",
+	"de",
+	"wm",
+	"cpu",
+	"cpu_usage",
+	"cpu_temp",
+	"gpu",
+	"gpu_temp",
+	"monitors",
+	"board",
+	"memory",
+	"battery",
+	"disk",
+	"disk_io",
+	"network",
+	"network_rate",
+	"bluetooth",
+	"colors",
+];
+
+/// Modules that are only printed when they're asked for, since collecting
+/// them takes `[info] sample_ms`.
+pub(crate) static SAMPLED: &[&str] = &["cpu_usage", "disk_io", "network_rate"];
+
+/// The contents of `config.toml`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+	pub info: InfoConfig,
+	pub layout: LayoutConfig,
+	pub logo: LogoConfig,
+}
+
+/// The `[info]` table, which controls the built-in info renderer.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct InfoConfig {
+	/// Which modules to print, in order. Modules that aren't listed aren't
+	/// collected either.
+	pub modules: Vec<String>,
+	/// Modules to drop from `modules`.
+	pub exclude: Vec<String>,
+	/// The string printed between a key and its value.
+	pub separator: String,
+	/// Replacement keys, indexed by module name.
+	pub labels: HashMap<String, String>,
+	pub colors: InfoColors,
+	/// How long to measure rates over, in milliseconds, for `cpu_usage`,
+	/// `disk_io` and `network_rate`. `--watch` measures them between
+	/// redraws instead.
+	pub sample_ms: u64,
+}
+
+/// The `[info.colors]` table. Unset colors fall back to the distro's colors.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct InfoColors {
+	/// The color of the `user@host` line.
+	pub title: Option<Color>,
+	/// The color of keys.
+	pub key: Option<Color>,
+	/// The color of values.
+	pub value: Option<Color>,
+}
+
+/// The `[layout]` table, which controls how the art and info are placed and
+/// fitted to the terminal's width.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct LayoutConfig {
+	/// Where the art goes, relative to the info.
+	pub art: Position,
+	/// Columns before every line.
+	pub padding: u16,
+	/// Columns between the art and info, or blank lines when they're stacked.
+	pub gap: u16,
+	/// Whether to centre the shorter of the art and info vertically, or the
+	/// narrower horizontally when they're stacked.
+	pub center: bool,
+	/// Whether to fit the output to the terminal when it's too wide: first
+	/// with the art's `_small` variant, then without the art, and finally by
+	/// cutting off info lines.
+	pub fit: bool,
+	/// The fewest columns the info can be cut down to while keeping the art.
+	/// Without it, the art is dropped unless the whole info fits beside it.
+	pub min_info_width: Option<u16>,
+	/// Always use the small art in terminals narrower than this.
+	pub small_art_below: Option<u16>,
+	/// Never draw the art in terminals narrower than this.
+	pub no_art_below: Option<u16>,
+}
+
+impl Default for LayoutConfig {
+	fn default() -> Self {
+		LayoutConfig {
+			art: Position::Left,
+			padding: 2,
+			gap: 2,
+			center: false,
+			fit: true,
+			min_info_width: None,
+			small_art_below: None,
+			no_art_below: None,
+		}
+	}
+}
+
+/// The `[logo]` table, which can replace the ASCII art with an image.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct LogoConfig {
+	/// An image to draw instead of the ASCII art, sized to the info's height.
+	/// Relative paths are relative to the config directory.
+	pub image: Option<PathBuf>,
+	pub protocol: Protocol,
+	/// A picture to draw as colored text instead of the ASCII art. It works
+	/// anywhere text does, and is the fallback when `image` can't be drawn.
+	pub ascii_from_image: Option<PathBuf>,
+	/// How many columns wide to draw `ascii_from_image`.
+	pub ascii_width: u16,
+	pub ascii_style: Style,
+	/// Recolors the ASCII art: `gradient:COLORS`, `vgradient:COLORS`,
+	/// `rainbow` or `palette`.
+	pub effect: Option<Effect>,
+}
+
+impl Default for LogoConfig {
+	fn default() -> Self {
+		LogoConfig {
+			image: None,
+			protocol: Protocol::Auto,
+			ascii_from_image: None,
+			ascii_width: 32,
+			ascii_style: Style::Blocks,
+			effect: None,
+		}
+	}
+}
+
+impl Default for InfoConfig {
+	fn default() -> Self {
+		InfoConfig {
+			modules: MODULES.iter()
+				.filter(|m| !SAMPLED.contains(m))
+				.map(|m| String::from(*m))
+				.collect(),
+			exclude: Vec::new(),
+			separator: String::from(": "),
+			labels: HashMap::new(),
+			colors: InfoColors::default(),
+			sample_ms: 250,
+		}
+	}
+}
+
+impl Config {
+	/// Loads `config.toml` from the config directory, with the selected
+	/// profile's `config.toml` merged on top. Missing files are skipped.
+	/// Each file is checked on its own, so errors name the file they're in.
+	pub fn load(paths: &Paths) -> errors::Result<Self> {
+		paths.check_profile()?;
+		let files = paths.configs();
+		let last = match files.last() {
+			Some(v) => v.to_string_lossy().into_owned(),
+			None => return Ok(Config::default()),
+		};
+		let mut table = Table::new();
+		for path in files.iter() {
+			let file = Config::read(path)?;
+			let name = path.to_string_lossy().into_owned();
+			Config::deserialize(file.clone())
+				.map_err(|e| e.to_string())
+				.and_then(|v| v.validate())
+				.map_err(|e| errors::FreshfetchError::Config(name, e))?;
+			merge(&mut table, file);
+		}
+		let mut config = Config::deserialize(table).map_err(|e| {
+			errors::FreshfetchError::Config(last, e.to_string())
+		})?;
+		let exclude = config.info.exclude.clone();
+		config.info.modules.retain(|m| !exclude.contains(m));
+		Ok(config)
+	}
+
+	fn read(path: &Path) -> errors::Result<Table> {
+		let file = fs::read_to_string(path).map_err(|e| {
+			errors::FreshfetchError::Io(path.to_string_lossy().into_owned(), e.to_string())
+		})?;
+		file.parse::<Table>().map_err(|e| {
+			errors::FreshfetchError::Config(path.to_string_lossy().into_owned(), e.to_string())
+		})
+	}
+
+	/// Applies `--only` and `--exclude`. `only` replaces the configured
+	/// module list, and `exclude` removes modules from it.
+	pub fn restrict(&mut self, only: Option<&[String]>, exclude: &[String]) -> errors::Result<()> {
+		let all = only.unwrap_or(&[]).iter().chain(exclude.iter());
+		check_modules(all).map_err(errors::FreshfetchError::General)?;
+		if let Some(only) = only {
+			self.info.modules = only.to_vec();
+		}
+		self.info.modules.retain(|m| !exclude.contains(m));
+		Ok(())
+	}
+
+	fn validate(&self) -> Result<(), String> {
+		check_modules(self.info.modules.iter()
+			.chain(self.info.exclude.iter())
+			.chain(self.info.labels.keys()))
+	}
+}
+
+impl InfoConfig {
+	/// Whether `module` will be printed, and therefore needs to be collected.
+	pub fn enabled(&self, module: &str) -> bool {
+		self.modules.iter().any(|m| m == module)
+	}
+}
+
+/// Merges `from` into `into`. Tables are merged key by key, and everything
+/// else, including arrays, is replaced.
+fn merge(into: &mut Table, from: Table) {
+	for (key, value) in from {
+		match (into.get_mut(&key), value) {
+			(Some(Value::Table(a)), Value::Table(b)) => merge(a, b),
+			(_, value) => { into.insert(key, value); }
+		}
+	}
+}
+
+fn check_modules<'a>(mut modules: impl Iterator<Item = &'a String>) -> Result<(), String> {
+	match modules.find(|m| !MODULES.contains(&m.as_str())) {
+		Some(module) => Err(format!("Unknown module \"{}\". Expected one of: {}.", module, MODULES.join(", "))),
+		None => Ok(()),
+	}
+}
+
+impl Inject for InfoConfig {
+	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
+		let globals = lua.globals();
+
+		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		let modules = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		for (i, module) in self.modules.iter().enumerate() {
+			modules.raw_set((i + 1) as i64, module.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		t.set("modules", modules).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		t.set("separator", self.separator.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		let labels = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		for (module, label) in self.labels.iter() {
+			labels.set(module.as_str(), label.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		t.set("labels", labels).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		let colors = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		if let Some(v) = &self.colors.title {
+			colors.set("title", v.escape()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		if let Some(v) = &self.colors.key {
+			colors.set("key", v.escape()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		if let Some(v) = &self.colors.value {
+			colors.set("value", v.escape()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+		t.set("colors", colors).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		globals.set("config", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		Ok(())
+	}
+}
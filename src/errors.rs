@@ -7,6 +7,7 @@ pub(crate) enum FreshfetchError {
     Lua(String),
     Command(String, String),
     Io(String, String),
+    Config(String, String),
     General(String),
 }
 
@@ -16,6 +17,7 @@ impl fmt::Display for FreshfetchError {
             FreshfetchError::Lua(details) => write!(f, "A Lua error occurred. Details:\n{}", details),
             FreshfetchError::Command(cmd, details) => write!(f, "An error occurred while executing \"{}\". Details:\n{}", cmd, details),
             FreshfetchError::Io(path, details) => write!(f, "An I/O error occurred while trying to read from \"{}\". Details:\n{}", path, details),
+            FreshfetchError::Config(path, details) => write!(f, "An error occurred while parsing the config file \"{}\". Details:\n{}", path, details),
             FreshfetchError::General(details) => write!(f, "An error occurred: {}", details),
         }
     }
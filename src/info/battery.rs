@@ -8,16 +8,22 @@ use mlua::prelude::*;
 use crate::Inject;
 
 /// Battery information for laptops
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Battery information for laptops
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Battery {
+    /// Charge, as a percentage.
+    #[serde(rename = "capacity_percent")]
     pub capacity: u8,
     pub status: String,
     pub cycle_count: Option<u32>,
-    pub health: Option<u8>,  // Percentage of original capacity
-    pub power_draw: Option<f32>,  // Watts
+    /// Percentage of original capacity.
+    #[serde(rename = "health_percent")]
+    pub health: Option<u8>,
+    #[serde(rename = "power_draw_watts")]
+    pub power_draw: Option<f32>,
 }
 
 impl Battery {
@@ -68,7 +74,7 @@ impl Battery {
         None
     }
     
-    fn calculate_health(bat_path: &std::path::PathBuf) -> Option<u8> {
+    fn calculate_health(bat_path: &Path) -> Option<u8> {
         let energy_full = fs::read_to_string(bat_path.join("energy_full"))
             .ok()
             .and_then(|s| s.trim().parse::<u64>().ok())?;
@@ -83,7 +89,7 @@ impl Battery {
         }
     }
     
-    fn calculate_power_draw(bat_path: &std::path::PathBuf) -> Option<f32> {
+    fn calculate_power_draw(bat_path: &Path) -> Option<f32> {
         // power_now is in microwatts
         let power_now = fs::read_to_string(bat_path.join("power_now"))
             .ok()
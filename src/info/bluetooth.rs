@@ -7,10 +7,11 @@ use std::process::Command;
 
 use mlua::prelude::*;
 use crate::Inject;
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Bluetooth device information
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct BluetoothDevice {
     pub name: String,
     pub mac: String,
@@ -18,7 +19,7 @@ pub(crate) struct BluetoothDevice {
 }
 
 /// Bluetooth adapter and devices
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Bluetooth {
     pub adapter: Option<String>,
     pub devices: Vec<BluetoothDevice>,
@@ -39,9 +40,7 @@ impl Bluetooth {
             .next()
             .map(|e| e.file_name().to_string_lossy().to_string());
         
-        if adapter.is_none() {
-            return None;
-        }
+        adapter.as_ref()?;
         
         // Try to get paired devices via bluetoothctl
         let devices = Self::get_paired_devices();
@@ -9,9 +9,10 @@ use crate::{ Inject };
 use std::fs::{ read_to_string };
 use std::env::{ var };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Context {
 	pub user: String,
 	pub host: String,
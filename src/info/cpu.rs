@@ -13,15 +13,17 @@ use regex::{ Regex };
 use crate::{ Inject }; 
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub(crate) struct Cpu {
 	/// The name of the CPU.
 	pub name: String,
 	/// The name of the CPU, without any information cut off.
 	pub full_name: String,
-	/// The frequency of the CPU, in MHz.
+	/// The frequency of the CPU, in GHz.
+	#[serde(rename = "freq_ghz")]
 	pub freq: f32,
 	/// The number of cores in the CPU.
 	pub cores: i32,
@@ -178,18 +180,6 @@ impl Cpu {
 	}
 }
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-
-	#[test]
-	fn test_clean_cpu_name() {
-		assert_eq!(Cpu::clean_cpu_name("Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz"), "Core i7-10700K @ 3.80GHz");
-		assert_eq!(Cpu::clean_cpu_name("AMD Ryzen 7 3700X 8-Core Processor"), "Ryzen 7 3700X");
-		assert_eq!(Cpu::clean_cpu_name("Intel(R) Core(TM)2 Duo CPU E8400 @ 3.00GHz"), "Core 2 Duo E8400 @ 3.00GHz");
-	}
-}
-
 impl Inject for Cpu {
 	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
 		let globals = lua.globals();
@@ -202,4 +192,16 @@ impl Inject for Cpu {
         globals.set("cpu", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         Ok(())
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_clean_cpu_name() {
+		assert_eq!(Cpu::clean_cpu_name("Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz"), "Core i7-10700K @ 3.80GHz");
+		assert_eq!(Cpu::clean_cpu_name("AMD Ryzen 7 3700X 8-Core Processor"), "Ryzen 7 3700X");
+		assert_eq!(Cpu::clean_cpu_name("Intel(R) Core(TM)2 Duo CPU E8400 @ 3.00GHz"), "Core 2 Duo E8400 @ 3.00GHz");
+	}
+}
@@ -15,24 +15,29 @@ use crate::{ Inject };
 use kernel::{ Kernel };
 use distro::{ Distro };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
-pub(crate) struct De ( pub String, pub String, );
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct De {
+	pub name: String,
+	/// Empty if the version couldn't be found.
+	pub version: String,
+}
 
 impl De {
 	pub fn new(k: &Kernel, d: &Distro) -> Option<Self> {
 		let to_return = match k.name.as_str() {
-			"Mac OS X"|"macOS" => Some(De(String::from("Aqua"), String::new())),
+			"Mac OS X"|"macOS" => Some(De { name: String::from("Aqua"), version: String::new() }),
 			_ => {
 				if d.short_name.starts_with("Windows") {
 					if d.short_name.starts_with("Windows 8") || d.short_name.starts_with("Windows 10") {
-						Some(De(String::from("Modern UI/Metro"), String::new()))
+						Some(De { name: String::from("Modern UI/Metro"), version: String::new() })
 					} else {
-						Some(De(String::from("Aero"), String::new()))
+						Some(De { name: String::from("Aero"), version: String::new() })
 					}
 				} else if if let Ok(desktop_session) = env::var("DESKTOP_SESSION") { desktop_session == "regolith" } else { false } {
-    						Some(De(String::from("Regolith"), String::new()))
+    						Some(De { name: String::from("Regolith"), version: String::new() })
     					} else if let Ok(mut current_desktop) = env::var("XDG_CURRENT_DESKTOP") {
     						current_desktop = current_desktop.replace("X-", "");
     						// The following is from neofetch, and I have
@@ -44,13 +49,13 @@ impl De {
     						// Unless somebody opens a PR with whatever
     						// that is in Rust, I'm just gonna pretend that
     						// code doesn't exist lol.
-    						Some(De(current_desktop, String::new()))
+    						Some(De { name: current_desktop, version: String::new() })
     					} else if env::var("GNOME_DESKTOP_SESSION_ID").is_ok() {
-    						Some(De(String::from("GNOME"), String::new()))
+    						Some(De { name: String::from("GNOME"), version: String::new() })
     					} else if env::var("MATE_DESKTOP_SESSION_ID").is_ok() {
-    						Some(De(String::from("MATE"), String::new()))
+    						Some(De { name: String::from("MATE"), version: String::new() })
     					} else if env::var("TDE_FULL_SESSION").is_ok() {
-    						Some(De(String::from("Trinity"), String::new()))
+    						Some(De { name: String::from("Trinity"), version: String::new() })
     					} else {
     						None
     					}
@@ -62,7 +67,7 @@ impl De {
 				.parse::<i32>()
 				.ok()
 				.unwrap_or(0) >= 4 {
-				to_return.0 = to_return.0.replace("KDE", "Plasma");
+				to_return.name = to_return.name.replace("KDE", "Plasma");
 			}
 			// Get version number.
 			{
@@ -77,23 +82,23 @@ impl De {
 						.unwrap_or_default()
 				};
 
-				if to_return.0.starts_with("Plasma") {
-					to_return.1 = get_version("plasmashell", &["--version"])
+				if to_return.name.starts_with("Plasma") {
+					to_return.version = get_version("plasmashell", &["--version"])
 						.replace("plasmashell ", "");
-				} else if to_return.0.starts_with("MATE") {
-					to_return.1 = get_version("mate-session", &["--version"]);
-				} else if to_return.0.starts_with("Xfce") {
-					to_return.1 = get_version("xfce4-session", &["--version"]);
-				} else if to_return.0.starts_with("GNOME") {
-					to_return.1 = get_version("gnome-shell", &["--version"]);
-				} else if to_return.0.starts_with("Cinnamon") {
-					to_return.1 = get_version("cinnamon", &["--version"]);
-				} else if to_return.0.starts_with("Budgie") {
-					to_return.1 = get_version("budgie-desktop", &["--version"]);
-				} else if to_return.0.starts_with("LXQt") {
-					to_return.1 = get_version("lxqt-session", &["--version"]);
-				} else if to_return.0.starts_with("Unity") {
-					to_return.1 = get_version("unity", &["--version"]);
+				} else if to_return.name.starts_with("MATE") {
+					to_return.version = get_version("mate-session", &["--version"]);
+				} else if to_return.name.starts_with("Xfce") {
+					to_return.version = get_version("xfce4-session", &["--version"]);
+				} else if to_return.name.starts_with("GNOME") {
+					to_return.version = get_version("gnome-shell", &["--version"]);
+				} else if to_return.name.starts_with("Cinnamon") {
+					to_return.version = get_version("cinnamon", &["--version"]);
+				} else if to_return.name.starts_with("Budgie") {
+					to_return.version = get_version("budgie-desktop", &["--version"]);
+				} else if to_return.name.starts_with("LXQt") {
+					to_return.version = get_version("lxqt-session", &["--version"]);
+				} else if to_return.name.starts_with("Unity") {
+					to_return.version = get_version("unity", &["--version"]);
 				}
 			}
 			Some(to_return)
@@ -108,8 +113,8 @@ impl Inject for De {
 		let globals = lua.globals();
 
 		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("name", self.0.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("version", self.1.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("name", self.name.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("version", self.version.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         globals.set("de", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         Ok(())
 	}
@@ -8,14 +8,17 @@ use sysinfo::Disks;
 use crate::Inject;
 
 /// Disk usage information
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Disk usage information
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Disk {
     pub name: String,
     pub mount_point: String,
+    #[serde(rename = "total_bytes")]
     pub total: u64,
+    #[serde(rename = "used_bytes")]
     pub used: u64,
     pub fs_type: String,
 }
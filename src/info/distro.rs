@@ -1,27 +1,45 @@
+use crate::dirs;
 use crate::mlua;
 use crate::regex::Regex;
 
+use crate::color;
 use crate::errors;
 use super::kernel;
 
+use std::collections::{ HashMap };
 use std::fs;
 use std::env;
-use std::path::{ Path };
+use std::path::{ Path, PathBuf };
 
 use mlua::prelude::*;
 
 
 
 use crate::{ Inject };
+use color::{ Color };
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Distro {
 	pub long_name: String,
 	pub short_name: String,
 	pub architecture: String,
+	/// `ID` from os-release, like `arch` or `ubuntu`.
+	pub id: Option<String>,
+	/// `ID_LIKE` from os-release: the distros this one is derived from.
+	pub id_like: Vec<String>,
+	pub version_id: Option<String>,
+	pub variant: Option<String>,
+	pub build_id: Option<String>,
+	/// `ANSI_COLOR` from os-release, parsed.
+	#[schemars(with = "Option<String>")]
+	pub ansi_color: Option<Color>,
+	/// `LOGO` from os-release: an icon name, like `archlinux-logo`. It's
+	/// only looked up in the icon themes when it's drawn, with `find_icon`.
+	pub logo: Option<String>,
 	pub colors: DistroColors,
 }
 
@@ -104,15 +122,87 @@ impl Distro {
 			}
 			_ => {} // Do nothing, unknown OS'es should have already exited by now.
 		}
+		let mut os_release = os_release();
+		let ansi_color = os_release.get("ANSI_COLOR")
+			.and_then(|v| Color::parse(&format!("\u{1b}[{}m", v)).ok());
+		let logo = os_release.remove("LOGO");
 		Distro {
 			long_name,
 			short_name,
 			architecture: k.architecture.clone(),
+			id: os_release.remove("ID"),
+			id_like: os_release.remove("ID_LIKE")
+				.map(|v| v.split_whitespace().map(String::from).collect())
+				.unwrap_or_default(),
+			version_id: os_release.remove("VERSION_ID"),
+			variant: os_release.remove("VARIANT"),
+			build_id: os_release.remove("BUILD_ID"),
+			ansi_color,
+			logo,
 			colors: DistroColors::new(),
 		}
 	}
 }
 
+/// Reads `/etc/os-release`, or `/usr/lib/os-release` if it doesn't exist.
+/// Empty values are left out.
+fn os_release() -> HashMap<String, String> {
+	["/etc/os-release", "/usr/lib/os-release"].iter()
+		.find_map(|file| fs::read_to_string(file).ok())
+		.map(|v| parse_os_release(&v))
+		.unwrap_or_default()
+}
+
+/// Parses os-release's `KEY=value` lines. Values may be quoted with `"` or
+/// `'`, and `\` escapes the next character inside double quotes.
+fn parse_os_release(content: &str) -> HashMap<String, String> {
+	let mut to_return = HashMap::new();
+	for line in content.lines() {
+		let line = line.trim();
+		if line.starts_with('#') { continue; }
+		let (key, raw) = match line.split_once('=') {
+			Some(v) => v,
+			None => continue,
+		};
+		let mut value = String::new();
+		let mut chars = raw.trim().chars();
+		let mut quote = None;
+		while let Some(c) = chars.next() {
+			match (quote, c) {
+				(None, '"') | (None, '\'') => quote = Some(c),
+				(Some(q), c) if c == q => quote = None,
+				(Some('"'), '\\') | (None, '\\') => value.extend(chars.next()),
+				(_, c) => value.push(c),
+			}
+		}
+		if !value.is_empty() {
+			to_return.insert(String::from(key.trim()), value);
+		}
+	}
+	to_return
+}
+
+/// Looks for a raster icon called `name` in the hicolor icon theme and in
+/// `pixmaps`, largest first. SVGs are skipped, since they can't be drawn.
+pub(crate) fn find_icon(name: &str) -> Option<PathBuf> {
+	let mut bases: Vec<PathBuf> = Vec::new();
+	if let Some(home) = dirs::home_dir() {
+		bases.push(home.join(".local/share"));
+	}
+	let data_dirs = env::var("XDG_DATA_DIRS").unwrap_or_default();
+	let data_dirs = if data_dirs.is_empty() { "/usr/local/share:/usr/share" } else { data_dirs.as_str() };
+	bases.extend(data_dirs.split(':').filter(|v| !v.is_empty()).map(PathBuf::from));
+	for base in bases.iter() {
+		for size in ["512x512", "256x256", "128x128", "96x96", "64x64", "48x48"] {
+			let path = base.join("icons/hicolor").join(size).join("apps").join(format!("{}.png", name));
+			if path.is_file() { return Some(path); }
+		}
+		let path = base.join("pixmaps").join(format!("{}.png", name));
+		if path.is_file() { return Some(path); }
+	}
+	None
+}
+
 impl Inject for Distro {
 	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
 		{
@@ -121,6 +211,15 @@ impl Inject for Distro {
 			t.set("long_name", self.long_name.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
 			t.set("short_name", self.short_name.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
 			t.set("architecture", self.architecture.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("id", self.id.as_deref()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("id_like", self.id_like.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("version_id", self.version_id.as_deref()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("variant", self.variant.as_deref()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("build_id", self.build_id.as_deref()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			if let Some(color) = &self.ansi_color {
+				t.set("ansi_color", color.describe(lua)?).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			}
+			t.set("logo", self.logo.as_deref()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
 			globals.set("distro", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
 		}
 		self.colors.inject(lua)?;
@@ -128,17 +227,33 @@ impl Inject for Distro {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
-pub(crate) struct DistroColors ( pub String, pub String, pub String, pub String );
+/// The distro's four palette colors, in the order the art's `${c1}` to
+/// `${c4}` use them. They're serialised as hex, or null for the terminal's
+/// default foreground.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub(crate) struct DistroColors {
+	#[serde(serialize_with = "Color::serialize_hex")]
+	#[schemars(with = "Option<String>")]
+	pub primary: Color,
+	#[serde(serialize_with = "Color::serialize_hex")]
+	#[schemars(with = "Option<String>")]
+	pub secondary: Color,
+	#[serde(serialize_with = "Color::serialize_hex")]
+	#[schemars(with = "Option<String>")]
+	pub tertiary: Color,
+	#[serde(serialize_with = "Color::serialize_hex")]
+	#[schemars(with = "Option<String>")]
+	pub quaternary: Color,
+}
 
 impl DistroColors {
 	pub fn new() -> Self {
-		DistroColors (
-			String::from("\u{001b}[38;5;7m"),
-			String::from("\u{001b}[38;5;7m"),
-			String::from("\u{001b}[38;5;7m"),
-			String::from("\u{001b}[38;5;7m"),
-		)
+		DistroColors::from([None, None, None, None])
+	}
+
+	/// All four colors, in order.
+	pub fn all(&self) -> [Color; 4] {
+		[self.primary, self.secondary, self.tertiary, self.quaternary]
 	}
 }
 
@@ -147,31 +262,58 @@ impl Inject for DistroColors {
 		let globals = lua.globals();
 
 		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.raw_insert(1, self.0.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.raw_insert(2, self.1.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.raw_insert(3, self.2.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.raw_insert(4, self.3.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		let palette = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		for (i, color) in self.all().iter().enumerate() {
+			t.raw_insert((i + 1) as i64, color.escape()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			palette.raw_insert((i + 1) as i64, color.describe(lua)?).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
         globals.set("distroColors", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        globals.set("distroPalette", palette).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         Ok(())
 	}
 }
 
-impl From<[Option<&'static str>; 4]> for DistroColors {
-	fn from(v: [Option<&'static str>; 4]) -> Self {
-		let color1 = String::from(v[0].unwrap_or("white"));
-		let mut color2 = String::from(v[1]
-			.unwrap_or(v[0]
-				.unwrap_or("\u{001b}[38;5;7m")));
-		let color3 = String::from(v[2]
-			.unwrap_or(v[1]
-				.unwrap_or(v[0]
-					.unwrap_or("\u{001b}[38;5;7m"))));
-		let color4 = String::from(
-			v[3].unwrap_or(
-				v[2].unwrap_or(v[1]
-					.unwrap_or(v[0]
-						.unwrap_or("\u{001b}[38;5;7m")))));
-		if color2 == "\u{001b}[38;5;7m" { color2 = color1.clone(); }
-		DistroColors ( color1, color2, color3, color4 )
+impl From<[Option<Color>; 4]> for DistroColors {
+	/// Missing colors fall back to the one before them, and then to white.
+	fn from(v: [Option<Color>; 4]) -> Self {
+		let white = Color::indexed(7);
+		let color1 = v[0].unwrap_or(white);
+		let mut color2 = v[1].or(v[0]).unwrap_or(white);
+		let color3 = v[2].or(v[1]).or(v[0]).unwrap_or(white);
+		let color4 = v[3].or(v[2]).or(v[1]).or(v[0]).unwrap_or(white);
+		if color2 == white { color2 = color1; }
+		DistroColors { primary: color1, secondary: color2, tertiary: color3, quaternary: color4 }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_os_release() {
+		let vars = parse_os_release(concat!(
+			"# comment\n",
+			"NAME=\"Arch Linux\"\n",
+			"ID=arch\n",
+			"ID_LIKE='rhel fedora'\n",
+			"ANSI_COLOR=\"38;2;23;147;209\"\n",
+			"VARIANT=\"Say \\\"hi\\\"\"\n",
+			"BUILD_ID=\n",
+		));
+		assert_eq!(vars.get("NAME").map(String::as_str), Some("Arch Linux"));
+		assert_eq!(vars.get("ID").map(String::as_str), Some("arch"));
+		assert_eq!(vars.get("ID_LIKE").map(String::as_str), Some("rhel fedora"));
+		assert_eq!(vars.get("VARIANT").map(String::as_str), Some("Say \"hi\""));
+		assert!(!vars.contains_key("BUILD_ID"));
+		let color = Color::parse(&format!("\u{1b}[{}m", vars["ANSI_COLOR"])).unwrap();
+		assert_eq!(color.kind, color::value::Kind::Rgb(23, 147, 209));
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn serialises_colors_as_hex() {
+		let colors = DistroColors::from([Some(Color::indexed(4)), Some(Color::DEFAULT), None, Some(Color::parse("#1793d1").unwrap())]);
+		assert_eq!(crate::serde_json::to_string(&colors).unwrap(),
+			r##"{"primary":"#0000ee","secondary":null,"tertiary":null,"quaternary":"#1793d1"}"##);
+	}
+}
@@ -12,9 +12,10 @@ use mlua::prelude::*;
 use crate::{ Inject };
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Gpu {
     pub brand: String,
     pub name: String,
@@ -30,7 +31,7 @@ impl Gpu {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Gpus ( pub Vec<Gpu> );
 
 impl Gpus {
@@ -12,9 +12,10 @@ use regex::{ Regex };
 use crate::{ Inject };
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Host {
     pub model: String,
 }
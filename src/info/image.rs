@@ -1,8 +1,9 @@
 use crate::mlua;
 use mlua::prelude::*;
 use viuer::{Config, print_from_file};
-use std::path::Path;
+use std::path::{ PathBuf };
 use crate::errors;
+use super::distro::{ find_icon };
 
 /// Manages terminal image rendering capabilities.
 pub(crate) struct ImageManager;
@@ -10,16 +11,23 @@ pub(crate) struct ImageManager;
 impl ImageManager {
     /// Injects the `image(path)` function into the provided Lua environment.
     /// This allows layouts to render images directly in the terminal.
-    pub fn inject(lua: &mut Lua) -> errors::Result<()> {
+    /// Without a path, `image()` draws `logo`, the distro's os-release logo,
+    /// which is only looked up in the icon themes then.
+    pub fn inject(lua: &mut Lua, logo: Option<String>) -> errors::Result<()> {
         let globals = lua.globals();
         
-        let image_fn = lua.create_function(|_, path: String| {
+        let image_fn = lua.create_function(move |_, path: Option<String>| {
+            let path = match path.map(PathBuf::from).or_else(|| logo.as_deref().and_then(find_icon)) {
+                Some(v) => v,
+                None => return Err(mlua::Error::RuntimeError(String::from(
+                    "Failed to render image: no path was given, and the distro's logo isn't installed."))),
+            };
             let config = Config {
                 restore_cursor: true,
                 ..Default::default()
             };
             
-            match print_from_file(Path::new(&path), &config) {
+            match print_from_file(&path, &config) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     // We return an error to Lua, which freshfetch will eventually catch
@@ -8,9 +8,10 @@ use uname::{ uname };
 
 use crate::{ Inject };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct Kernel {
 	pub name: String,
 	pub version: String,
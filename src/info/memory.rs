@@ -8,11 +8,14 @@ use mlua::prelude::*;
 use crate::{ Inject };
 use utils::{ get_system };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Memory {
+	#[serde(rename = "total_bytes")]
 	pub max: u64,
+	#[serde(rename = "used_bytes")]
 	pub used: u64,
 }
 
@@ -3,6 +3,8 @@ use crate::mlua;
 use crate::errors;
 use crate::assets;
 use crate::assets::defaults;
+use crate::config;
+use crate::paths;
 pub(crate) mod kernel;
 pub(crate) mod context;
 pub(crate) mod distro;
@@ -25,15 +27,19 @@ pub(crate) mod network;
 pub(crate) mod temperature;
 pub(crate) mod bluetooth;
 pub(crate) mod monitors;
+pub(crate) mod rates;
 
 use std::fs;
 use std::path::PathBuf;
+use std::time::{ Duration };
 
 use mlua::prelude::*;
 
 use crate::{ Inject };
 use assets::{ ANSI, PRINT };
 use defaults::{ INFO };
+use config::{ Config, InfoConfig };
+use paths::{ Paths };
 use utils::{ get_system };
 use kernel::{ Kernel };
 use context::{ Context };
@@ -55,31 +61,35 @@ use network::Network;
 use temperature::Temperature;
 use bluetooth::Bluetooth;
 use monitors::Monitors;
+use rates::{ Rates, Sample, Wants };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct Info {
-	#[serde(skip)]
-	ctx: Lua,
 	#[serde(skip)]
 	rendered: String,
 	#[serde(skip)]
 	width: i32,
 	#[serde(skip)]
 	height: i32,
+	#[serde(skip)]
+	config: InfoConfig,
+	#[serde(skip)]
+	script: Option<PathBuf>,
 	pub context: Option<Context>,
 	pub distro: Distro,
 	pub kernel: Kernel,
-	pub uptime: Uptime,
-	pub package_managers: PackageManagers,
-	pub shell: Shell,
+	pub uptime: Option<Uptime>,
+	pub package_managers: Option<PackageManagers>,
+	pub shell: Option<Shell>,
 	pub resolution: Option<Resolution>,
 	pub de: Option<De>,
 	pub wm: Option<Wm>,
 	pub cpu: Option<Cpu>,
 	pub gpu: Option<Gpus>,
-	pub memory: Memory,
+	pub memory: Option<Memory>,
 	pub motherboard: Option<Motherboard>,
 	pub host: Option<Host>,
 	pub battery: Option<Battery>,
@@ -88,69 +98,86 @@ pub(crate) struct Info {
 	pub temperature: Option<Temperature>,
 	pub bluetooth: Option<Bluetooth>,
 	pub monitors: Option<Monitors>,
+	pub rates: Option<Rates>,
 }
 
 impl Info {
-	pub fn new() -> errors::Result<Self> {
+	pub fn new(config: &Config, paths: &Paths) -> errors::Result<Self> {
+		// Modules that aren't enabled are never collected.
+		let wants = |module: &str| config.info.enabled(module);
+
 		{
 			let mut system = get_system();
-			system.refresh_cpu_usage();
-			system.refresh_memory();
+			if wants("cpu") { system.refresh_cpu_usage(); }
+			if wants("memory") { system.refresh_memory(); }
 		}
-		
+
+		// Rates need two samples, so the first is taken before everything
+		// else is collected, and the second once that's done, at least
+		// `sample_ms` later.
+		let sampled = Wants {
+			cpu: wants("cpu_usage"),
+			disks: wants("disk_io"),
+			network: wants("network_rate"),
+		};
+		let first = if sampled.any() { Some(Sample::take(sampled)) } else { None };
+
 		// Sequential: Kernel must be first since others depend on it
 		let kernel = Kernel::new()?;
-		let context = Context::new();
+		let context = if wants("context") { Context::new() } else { None };
+		// Distro is always needed, since the ASCII art is picked from it.
 		let distro = Distro::new(&kernel);
-		let uptime = Uptime::new(&kernel)?;
-		let package_managers = PackageManagers::new(&kernel)?;
-		let shell = Shell::new(&kernel)?;
+		let uptime = if wants("uptime") { Some(Uptime::new(&kernel)?) } else { None };
+		let package_managers = if wants("packages") { Some(PackageManagers::new(&kernel)?) } else { None };
+		let shell = if wants("shell") { Some(Shell::new(&kernel)?) } else { None };
 		
 		// Parallel: Independent info gathering using rayon
 		// Use nested joins in pairs for parallel execution
 		let ((resolution, de), (wm, cpu)) = rayon::join(
 			|| rayon::join(
-				|| Resolution::new(&kernel),
-				|| De::new(&kernel, &distro),
+				|| if wants("resolution") { Resolution::new(&kernel) } else { None },
+				|| if wants("de") { De::new(&kernel, &distro) } else { None },
 			),
 			|| rayon::join(
-				|| Wm::new(&kernel),
-				|| Cpu::new(&kernel),
+				|| if wants("wm") { Wm::new(&kernel) } else { None },
+				|| if wants("cpu") { Cpu::new(&kernel) } else { None },
 			),
 		);
 		
 		let ((gpu, motherboard), (host, battery)) = rayon::join(
 			|| rayon::join(
-				|| Gpus::new(&kernel),
-				|| Motherboard::new(&kernel),
+				|| if wants("gpu") { Gpus::new(&kernel) } else { None },
+				|| if wants("board") { Motherboard::new(&kernel) } else { None },
 			),
 			|| rayon::join(
-				|| Host::new(&kernel),
-				Battery::new,
+				|| if wants("host") { Host::new(&kernel) } else { None },
+				|| if wants("battery") { Battery::new() } else { None },
 			),
 		);
 		
 		let (((disk, network), (temperature, bluetooth)), monitors) = rayon::join(
 			|| rayon::join(
 				|| rayon::join(
-					Disk::new,
-					Network::new,
+					|| if wants("disk") { Disk::new() } else { None },
+					|| if wants("network") { Network::new() } else { None },
 				),
 				|| rayon::join(
-					Temperature::new,
-					Bluetooth::new,
+					|| if wants("cpu_temp") || wants("gpu_temp") { Temperature::new() } else { None },
+					|| if wants("bluetooth") { Bluetooth::new() } else { None },
 				),
 			),
-			|| Monitors::new(&kernel),
+			|| if wants("monitors") { Monitors::new(&kernel) } else { None },
 		);
 		
-		let memory = Memory::new();
+		let memory = if wants("memory") { Some(Memory::new()) } else { None };
+		let rates = first.map(|v| Rates::new(v, Duration::from_millis(config.info.sample_ms)));
 		
 		Ok(Info {
-			ctx: Lua::new(),
 			rendered: String::new(),
 			width: 0,
 			height: 0,
+			config: config.info.clone(),
+			script: paths.info(),
 			context,
 			distro,
 			kernel,
@@ -171,29 +198,76 @@ impl Info {
 			temperature,
 			bluetooth,
 			monitors,
+			rates,
 		})
 	}
-	pub fn render(&mut self) -> errors::Result<()> {
-		self.ctx.load(PRINT).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-		self.ctx.load(ANSI).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-
-		let info = dirs::home_dir()
-			.unwrap_or_else(|| PathBuf::from("."))
-			.join(".config/freshfetch/info.lua");
-		if info.exists() {
-			let file = fs::read_to_string(&info).map_err(|e| {
+
+	/// Re-collects the modules that change while freshfetch runs, for
+	/// `--watch`: uptime, memory, CPU, temperatures, battery, network and
+	/// disk, and the rates since the last time. The rest, like the distro
+	/// and GPUs, are kept as they were.
+	pub fn refresh(&mut self) -> errors::Result<()> {
+		let wants = |module: &str| self.config.enabled(module);
+		let (uptime, memory, cpu, battery) = (wants("uptime"), wants("memory"), wants("cpu"), wants("battery"));
+		{
+			let mut system = get_system();
+			if cpu { system.refresh_cpu_usage(); }
+			if memory { system.refresh_memory(); }
+		}
+		let (disk, network, temperature) = (wants("disk"), wants("network"), wants("cpu_temp") || wants("gpu_temp"));
+		if uptime { self.uptime = Some(Uptime::new(&self.kernel)?); }
+		if memory { self.memory = Some(Memory::new()); }
+		if cpu { self.cpu = Cpu::new(&self.kernel); }
+		if battery { self.battery = Battery::new(); }
+		if disk { self.disk = Disk::new(); }
+		if network { self.network = Network::new(); }
+		if temperature { self.temperature = Temperature::new(); }
+		if let Some(v) = &mut self.rates { v.update(); }
+		Ok(())
+	}
+
+	/// The output of the info script. Empty until `prep` is called.
+	pub fn rendered(&self) -> &str {
+		&self.rendered
+	}
+
+	pub fn width(&self) -> i32 {
+		self.width
+	}
+
+	/// Cuts every line of the rendered info down to `width` columns.
+	pub fn truncate(&mut self, width: i32) {
+		let width = width.max(0) as usize;
+		self.rendered = self.rendered
+			.split('\n')
+			.map(|line| crate::utils::ansi::truncate(line, width))
+			.collect::<Vec<String>>()
+			.join("\n");
+		let (w, h) = crate::utils::get_dimensions(&self.rendered);
+		self.width = w;
+		self.height = h;
+	}
+
+	/// Runs the info script in `ctx`, which must already have every module
+	/// injected.
+	fn render(&mut self, ctx: &Lua) -> errors::Result<()> {
+		ctx.load(PRINT).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		ctx.load(ANSI).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		if let Some(info) = &self.script {
+			let file = fs::read_to_string(info).map_err(|e| {
                 errors::FreshfetchError::Io(info.to_string_lossy().into_owned(), e.to_string())
             })?;
             
-            self.ctx.load(&file).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+            ctx.load(&file).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
             
-            self.rendered = self.ctx.globals().get::<&str, String>("__freshfetch__").map_err(|e| {
+            self.rendered = ctx.globals().get::<&str, String>("__freshfetch__").map_err(|e| {
                 errors::FreshfetchError::Lua(e.to_string())
             })?;
 		} else {
-			self.ctx.load(INFO).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			ctx.load(INFO).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
             
-            self.rendered = self.ctx.globals().get::<&str, String>("__freshfetch__").map_err(|e| {
+            self.rendered = ctx.globals().get::<&str, String>("__freshfetch__").map_err(|e| {
                 errors::FreshfetchError::Lua(e.to_string())
             })?;
 		}
@@ -203,28 +277,35 @@ impl Info {
 
 impl Inject for Info {
 	fn prep(&mut self) -> errors::Result<()> {
-		image::ImageManager::inject(&mut self.ctx)?;
-		if let Some(v) = &self.context { v.inject(&mut self.ctx)?; }
-		self.kernel.inject(&mut self.ctx)?;
-		self.distro.inject(&mut self.ctx)?;
-		self.uptime.inject(&mut self.ctx)?;
-		self.package_managers.inject(&mut self.ctx)?;
-		self.shell.inject(&mut self.ctx)?;
-		if let Some(v) = &self.resolution { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.wm { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.de { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.cpu { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.gpu { v.inject(&mut self.ctx)?; }
-		self.memory.inject(&mut self.ctx)?;
-        if let Some(v) = &self.motherboard { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.host { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.battery { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.disk { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.network { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.temperature { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.bluetooth { v.inject(&mut self.ctx)?; }
-		if let Some(v) = &self.monitors { v.inject(&mut self.ctx)?; }
-		self.render()?;
+		// The Lua state is only created here, so that the structured outputs
+		// never need one.
+		let mut ctx = Lua::new();
+		image::ImageManager::inject(&mut ctx, self.distro.logo.clone())?;
+		crate::utils::dimensions::inject(&mut ctx)?;
+		rates::inject(&mut ctx)?;
+		self.config.inject(&mut ctx)?;
+		if let Some(v) = &self.context { v.inject(&mut ctx)?; }
+		self.kernel.inject(&mut ctx)?;
+		self.distro.inject(&mut ctx)?;
+		if let Some(v) = &self.uptime { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.package_managers { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.shell { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.resolution { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.wm { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.de { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.cpu { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.gpu { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.memory { v.inject(&mut ctx)?; }
+        if let Some(v) = &self.motherboard { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.host { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.battery { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.disk { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.network { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.temperature { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.bluetooth { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.monitors { v.inject(&mut ctx)?; }
+		if let Some(v) = &self.rates { v.inject(&mut ctx)?; }
+		self.render(&ctx)?;
 		{
 			let (w, h) = crate::utils::get_dimensions(&self.rendered);
 			self.width = w;
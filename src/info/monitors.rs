@@ -7,10 +7,11 @@ use std::path::Path;
 
 use mlua::prelude::*;
 use crate::Inject;
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Monitor information parsed from EDID
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Monitor {
     pub name: String,
     pub width_mm: Option<u32>,
@@ -18,7 +19,7 @@ pub(crate) struct Monitor {
 }
 
 /// Collection of detected monitors
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Monitors {
     pub monitors: Vec<Monitor>,
 }
@@ -76,7 +77,7 @@ impl Monitors {
                 // Monitor names are up to 13 bytes, often ended with 0x0A (LF)
                 let name_bytes = &descriptor[5..18];
                 let name_str = String::from_utf8_lossy(name_bytes)
-                    .trim_end_matches(|c: char| c == '\n' || c == '\r' || c == ' ' || c == '\0')
+                    .trim_end_matches(['\n', '\r', ' ', '\0'])
                     .to_string();
                 if !name_str.is_empty() {
                     name = Some(name_str);
@@ -14,9 +14,10 @@ use mlua::prelude::*;
 use crate::{ Inject };
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub struct Motherboard {
     pub name: String,
     pub vendor: String,
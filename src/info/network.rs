@@ -8,10 +8,11 @@ use mlua::prelude::*;
 use crate::Inject;
 
 /// Network interface information
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Network interface information
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Network {
     pub interface: String,
     pub ip: String,
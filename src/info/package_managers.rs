@@ -11,9 +11,10 @@ use mlua::prelude::*;
 use crate::Inject;
 use kernel::Kernel;
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct PackageManager {
 	pub name: String,
 	pub packages: i32,
@@ -28,7 +29,7 @@ impl PackageManager {
 	}
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct PackageManagers(pub Vec<PackageManager>);
 
 impl PackageManagers {
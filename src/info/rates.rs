@@ -0,0 +1,349 @@
+use crate::mlua;
+use crate::errors;
+
+use std::fs;
+use std::path::{ Path };
+use std::thread;
+use std::time::{ Duration, Instant };
+
+use mlua::prelude::*;
+
+use crate::{ Inject };
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// How many past values each rate keeps for sparklines.
+const HISTORY: usize = 32;
+
+/// `/proc/diskstats` counts 512 byte sectors, whatever the disk's own
+/// sector size.
+const SECTOR: u64 = 512;
+
+/// Which rates to measure, from whether `cpu_usage`, `disk_io` and
+/// `network_rate` are enabled.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Wants {
+	pub cpu: bool,
+	pub disks: bool,
+	pub network: bool,
+}
+
+impl Wants {
+	pub fn any(&self) -> bool {
+		self.cpu || self.disks || self.network
+	}
+}
+
+/// Counters read at one moment. Rates are the difference between two.
+/// Counters that aren't wanted are left empty, without being read.
+#[derive(Debug)]
+pub(crate) struct Sample {
+	at: Instant,
+	wants: Wants,
+	/// Each interface's bytes received and sent.
+	network: Vec<(String, u64, u64)>,
+	/// Each disk's bytes read and written.
+	disks: Vec<(String, u64, u64)>,
+	/// Time spent busy and in total, for every core together, then for each
+	/// core.
+	cpu: Vec<(u64, u64)>,
+}
+
+impl Sample {
+	pub fn take(wants: Wants) -> Self {
+		// Only whole disks are listed in `/sys/block`, not partitions.
+		let is_disk = |name: &str| !name.starts_with("loop")
+			&& !name.starts_with("ram")
+			&& Path::new("/sys/block").join(name).exists();
+		Sample {
+			at: Instant::now(),
+			wants,
+			network: if wants.network { network() } else { Vec::new() },
+			disks: if !wants.disks { Vec::new() } else {
+				fs::read_to_string("/proc/diskstats")
+					.map(|v| parse_diskstats(&v))
+					.unwrap_or_default()
+					.into_iter()
+					.filter(|(name, _, _)| is_disk(name))
+					.collect()
+			},
+			cpu: if !wants.cpu { Vec::new() } else {
+				fs::read_to_string("/proc/stat").map(|v| parse_stat(&v)).unwrap_or_default()
+			},
+		}
+	}
+}
+
+/// Network throughput, for `network_rate`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct InterfaceRate {
+	pub interface: String,
+	pub rx_bytes_per_second: f64,
+	pub tx_bytes_per_second: f64,
+	#[serde(skip)]
+	rx_history: Vec<f64>,
+	#[serde(skip)]
+	tx_history: Vec<f64>,
+}
+
+/// Disk throughput, for `disk_io`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct DiskRate {
+	pub name: String,
+	pub read_bytes_per_second: f64,
+	pub write_bytes_per_second: f64,
+	#[serde(skip)]
+	read_history: Vec<f64>,
+	#[serde(skip)]
+	write_history: Vec<f64>,
+}
+
+/// CPU utilisation, for `cpu_usage`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CpuUsage {
+	/// Every core together.
+	pub usage_percent: f64,
+	pub cores_percent: Vec<f64>,
+	#[serde(skip)]
+	history: Vec<f64>,
+}
+
+/// Rates measured between two samples: over `[info] sample_ms` for a single
+/// run, and between redraws with `--watch`, which also keeps their history.
+/// Rates that weren't wanted are `None`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct Rates {
+	/// How long the rates were measured over.
+	pub window_seconds: f64,
+	pub network: Option<Vec<InterfaceRate>>,
+	pub disks: Option<Vec<DiskRate>>,
+	pub cpu: Option<CpuUsage>,
+	#[serde(skip)]
+	last: Sample,
+}
+
+impl Rates {
+	/// Measures the rates since `first`, waiting until at least `window` has
+	/// passed since it was taken.
+	pub fn new(first: Sample, window: Duration) -> Self {
+		thread::sleep(window.saturating_sub(first.at.elapsed()));
+		let mut to_return = Rates {
+			window_seconds: 0.0,
+			network: None,
+			disks: None,
+			cpu: None,
+			last: first,
+		};
+		to_return.update();
+		to_return
+	}
+
+	/// Measures the rates since the last sample, and adds them to their
+	/// history.
+	pub fn update(&mut self) {
+		let wants = self.last.wants;
+		let next = Sample::take(wants);
+		let seconds = next.at.duration_since(self.last.at).as_secs_f64().max(f64::EPSILON);
+		let per_second = |now: u64, then: u64| now.saturating_sub(then) as f64 / seconds;
+
+		let old_network = self.network.as_deref().unwrap_or(&[]);
+		let network = next.network.iter().map(|(name, rx, tx)| {
+			let (then_rx, then_tx) = counters(&self.last.network, name).unwrap_or((*rx, *tx));
+			let (rx, tx) = (per_second(*rx, then_rx), per_second(*tx, then_tx));
+			let old = old_network.iter().find(|v| &v.interface == name);
+			InterfaceRate {
+				interface: name.clone(),
+				rx_bytes_per_second: rx,
+				tx_bytes_per_second: tx,
+				rx_history: extend(old.map(|v| v.rx_history.as_slice()), rx),
+				tx_history: extend(old.map(|v| v.tx_history.as_slice()), tx),
+			}
+		}).collect();
+
+		let old_disks = self.disks.as_deref().unwrap_or(&[]);
+		let disks = next.disks.iter().map(|(name, read, write)| {
+			let (then_read, then_write) = counters(&self.last.disks, name).unwrap_or((*read, *write));
+			let (read, write) = (per_second(*read, then_read), per_second(*write, then_write));
+			let old = old_disks.iter().find(|v| &v.name == name);
+			DiskRate {
+				name: name.clone(),
+				read_bytes_per_second: read,
+				write_bytes_per_second: write,
+				read_history: extend(old.map(|v| v.read_history.as_slice()), read),
+				write_history: extend(old.map(|v| v.write_history.as_slice()), write),
+			}
+		}).collect();
+
+		let usage: Vec<f64> = next.cpu.iter().enumerate()
+			.map(|(i, now)| percent(*now, self.last.cpu.get(i).copied().unwrap_or((0, 0))))
+			.collect();
+		let total = usage.first().copied().unwrap_or(0.0);
+		let cpu = CpuUsage {
+			usage_percent: total,
+			cores_percent: usage.iter().skip(1).copied().collect(),
+			history: extend(self.cpu.as_ref().map(|v| v.history.as_slice()), total),
+		};
+
+		self.window_seconds = seconds;
+		self.network = wants.network.then_some(network);
+		self.disks = wants.disks.then_some(disks);
+		self.cpu = wants.cpu.then_some(cpu);
+		self.last = next;
+	}
+}
+
+fn counters(of: &[(String, u64, u64)], name: &str) -> Option<(u64, u64)> {
+	of.iter().find(|v| v.0 == name).map(|v| (v.1, v.2))
+}
+
+/// `history` with `value` added, keeping the last `HISTORY` values.
+fn extend(history: Option<&[f64]>, value: f64) -> Vec<f64> {
+	let history = history.unwrap_or(&[]);
+	let skip = (history.len() + 1).saturating_sub(HISTORY);
+	history.iter().skip(skip).copied().chain(std::iter::once(value)).collect()
+}
+
+/// How much of the time between two `(busy, total)` readings was busy.
+fn percent(now: (u64, u64), then: (u64, u64)) -> f64 {
+	let total = now.1.saturating_sub(then.1);
+	if total == 0 { return 0.0; }
+	now.0.saturating_sub(then.0) as f64 / total as f64 * 100.0
+}
+
+/// The byte counters of every interface that's up, other than loopback.
+fn network() -> Vec<(String, u64, u64)> {
+	let entries = match fs::read_dir("/sys/class/net") {
+		Ok(v) => v,
+		Err(_) => return Vec::new(),
+	};
+	let mut to_return: Vec<(String, u64, u64)> = entries.flatten().filter_map(|entry| {
+		let name = entry.file_name().to_string_lossy().into_owned();
+		if name == "lo" { return None; }
+		if let Ok(state) = fs::read_to_string(entry.path().join("operstate")) {
+			if state.trim() != "up" { return None; }
+		}
+		let read = |file: &str| -> Option<u64> {
+			fs::read_to_string(entry.path().join("statistics").join(file)).ok()?.trim().parse().ok()
+		};
+		Some((name, read("rx_bytes")?, read("tx_bytes")?))
+	}).collect();
+	to_return.sort();
+	to_return
+}
+
+/// Each device's name and bytes read and written.
+fn parse_diskstats(stats: &str) -> Vec<(String, u64, u64)> {
+	stats.lines().filter_map(|line| {
+		let fields: Vec<&str> = line.split_whitespace().collect();
+		let sectors = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok());
+		Some((String::from(*fields.get(2)?), sectors(5)? * SECTOR, sectors(9)? * SECTOR))
+	}).collect()
+}
+
+/// Time spent busy and in total, from the `cpu` lines. Guest time is
+/// already counted in user time, so only the first eight columns count.
+fn parse_stat(stat: &str) -> Vec<(u64, u64)> {
+	stat.lines()
+		.filter(|v| v.starts_with("cpu"))
+		.map(|line| {
+			let times: Vec<u64> = line.split_whitespace().skip(1).take(8).filter_map(|v| v.parse().ok()).collect();
+			let total: u64 = times.iter().sum();
+			// Idle and iowait.
+			let idle = times.get(3).copied().unwrap_or(0) + times.get(4).copied().unwrap_or(0);
+			(total.saturating_sub(idle), total)
+		})
+		.collect()
+}
+
+/// `values` as a row of block characters, scaled so that `max` is a full
+/// block. `max` defaults to the largest value.
+pub(crate) fn sparkline(values: &[f64], max: Option<f64>) -> String {
+	const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+	let max = max.unwrap_or_else(|| values.iter().copied().fold(0.0, f64::max));
+	values.iter().map(|v| {
+		if max <= 0.0 { return BARS[0]; }
+		BARS[((v / max).clamp(0.0, 1.0) * (BARS.len() - 1) as f64).round() as usize]
+	}).collect()
+}
+
+/// Injects `sparkline(values, max)`, for drawing the rates' history.
+pub(crate) fn inject(lua: &mut Lua) -> errors::Result<()> {
+	let sparkline_fn = lua.create_function(|_, (values, max): (Vec<f64>, Option<f64>)| {
+		Ok(sparkline(&values, max))
+	}).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	lua.globals().set("sparkline", sparkline_fn).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+	Ok(())
+}
+
+impl Inject for Rates {
+	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
+		let globals = lua.globals();
+
+		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		t.set("window_seconds", self.window_seconds).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+		if let Some(rates) = &self.network {
+			let network = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			for (i, v) in rates.iter().enumerate() {
+				let entry = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("interface", v.interface.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("rx_bytes_per_second", v.rx_bytes_per_second).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("tx_bytes_per_second", v.tx_bytes_per_second).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("rx_history", v.rx_history.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("tx_history", v.tx_history.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				network.raw_set((i + 1) as i64, entry).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			}
+			t.set("network", network).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+
+		if let Some(rates) = &self.disks {
+			let disks = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			for (i, v) in rates.iter().enumerate() {
+				let entry = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("name", v.name.as_str()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("read_bytes_per_second", v.read_bytes_per_second).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("write_bytes_per_second", v.write_bytes_per_second).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("read_history", v.read_history.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				entry.set("write_history", v.write_history.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+				disks.raw_set((i + 1) as i64, entry).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			}
+			t.set("disks", disks).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+
+		if let Some(usage) = &self.cpu {
+			let cpu = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			cpu.set("usage_percent", usage.usage_percent).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			cpu.set("cores_percent", usage.cores_percent.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			cpu.set("history", usage.history.clone()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+			t.set("cpu", cpu).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		}
+
+		globals.set("rates", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_counters() {
+		let stat = "cpu  100 0 100 700 100 0 0 0 50 0\ncpu0 50 0 50 350 50 0 0 0 0 0\nintr 1 2 3\n";
+		assert_eq!(parse_stat(stat), vec![(200, 1000), (100, 500)]);
+		assert_eq!(percent((300, 1200), (200, 1000)), 50.0);
+		let diskstats = "   8       0 sda 10 0 4 0 20 0 8 0 0 0 0\n   8       1 sda1 1 0 2\n";
+		assert_eq!(parse_diskstats(diskstats), vec![(String::from("sda"), 2048, 4096)]);
+	}
+
+	#[test]
+	fn draws_sparklines() {
+		assert_eq!(sparkline(&[0.0, 50.0, 100.0], Some(100.0)), "▁▅█");
+		assert_eq!(sparkline(&[1.0, 2.0], None), "▅█");
+		assert_eq!(sparkline(&[0.0, 0.0], None), "▁▁");
+		let history: Vec<f64> = (0..HISTORY).map(|v| v as f64).collect();
+		assert_eq!(extend(Some(&history), 99.0).len(), HISTORY);
+		assert_eq!(extend(None, 1.0), vec![1.0]);
+	}
+}
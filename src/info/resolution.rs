@@ -17,12 +17,14 @@ use crate::{ Inject };
 use utils::{ which::{ which } };
 use kernel::{ Kernel };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Resolution {
 	pub width: u16,
 	pub height: u16,
+    #[serde(rename = "refresh_hz")]
     pub refresh: Option<f32>,
 }
 
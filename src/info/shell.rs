@@ -12,9 +12,10 @@ use mlua::prelude::*;
 use crate::Inject;
 use kernel::Kernel;
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct Shell {
 	pub name: String,
 	pub version: String,
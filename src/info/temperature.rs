@@ -6,19 +6,23 @@ use mlua::prelude::*;
 use sysinfo::Components;
 
 use crate::Inject;
+use schemars::JsonSchema;
 use serde::Serialize;
 
 /// Temperature sensor reading
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct TempSensor {
     pub label: String,
+    #[serde(rename = "temp_celsius")]
     pub temp: f32,
+    #[serde(rename = "max_celsius")]
     pub max: Option<f32>,
+    #[serde(rename = "critical_celsius")]
     pub critical: Option<f32>,
 }
 
 /// System temperature information
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub(crate) struct Temperature {
     pub sensors: Vec<TempSensor>,
 }
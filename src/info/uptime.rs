@@ -1,4 +1,3 @@
-use crate::chrono;
 use crate::sysinfo;
 use crate::mlua;
 
@@ -6,9 +5,10 @@ use crate::errors;
 use super::kernel;
 
 use std::path::{ Path };
+use std::time::{ SystemTime, UNIX_EPOCH };
 
 use mlua::prelude::*;
-use chrono::{ Utc, DateTime, Datelike, Timelike, TimeZone };
+use schemars::JsonSchema;
 use sysinfo::{ System };
 
 use crate::{ Inject };
@@ -16,27 +16,29 @@ use kernel::{ Kernel };
 
 use serde::Serialize;
 
-#[derive(Serialize)]
-pub(crate) struct Uptime ( pub DateTime<Utc> );
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct Uptime {
+	/// How long the system has been up, in seconds.
+	pub seconds: u64,
+}
 
 impl Uptime {
 	pub fn new(k: &Kernel) -> errors::Result<Self> {
-		let uptime_seconds;
-		match k.name.as_str() {
+		let seconds = match k.name.as_str() {
 			"Linux"|"Windows"|"MINIX" => {
 				if Path::new("/proc/uptime").exists() {
-					uptime_seconds = System::uptime() as i64;
+					System::uptime()
 				} else {
-					let boot_time = System::boot_time() as i64;
-					let now_time = Utc::now().timestamp();
-					uptime_seconds = boot_time - now_time;
+					let now = SystemTime::now()
+						.duration_since(UNIX_EPOCH)
+						.map_err(|e| errors::FreshfetchError::General(format!("Failed to get the current time: {}", e)))?
+						.as_secs();
+					now.saturating_sub(System::boot_time())
 				}
 			}
-			_ => { uptime_seconds = 0; }
-		}
-		Ok(Uptime(Utc.timestamp_opt(uptime_seconds, 0)
-            .single()
-            .ok_or_else(|| errors::FreshfetchError::General(format!("Failed to create timestamp for uptime: {}", uptime_seconds)))?))
+			_ => 0,
+		};
+		Ok(Uptime { seconds })
 	}
 }
 
@@ -45,10 +47,11 @@ impl Inject for Uptime {
 		let globals = lua.globals();
 
 		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("days", self.0.ordinal0()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("hours", self.0.hour()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("minutes", self.0.minute()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
-        t.set("seconds", self.0.second()).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("days", self.seconds / 86400).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("hours", self.seconds / 3600 % 24).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("minutes", self.seconds / 60 % 60).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("seconds", self.seconds % 60).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("total", self.seconds).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         globals.set("uptime", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         Ok(())
 	}
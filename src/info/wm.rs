@@ -14,9 +14,10 @@ use crate::{ Inject };
 use kernel::{ Kernel };
 use utils::{ PsAux, Grep };
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub(crate) struct Wm ( pub String );
 
 impl Wm {
@@ -4,6 +4,13 @@ use crate::errors;
 use crate::misc;
 use crate::art;
 use crate::info;
+use crate::config;
+use crate::compose;
+use crate::assets;
+use crate::utils;
+
+use std::fs;
+use std::path::{ PathBuf };
 
 use mlua::prelude::*;
 
@@ -11,24 +18,158 @@ use crate::{ Inject, Arguments };
 use misc::{ Terminal };
 use art::{ Art };
 use info::{ Info };
+use config::{ Config, LayoutConfig };
+use compose::{ Options };
+use assets::{ ANSI, PRINT };
 
 pub(crate) struct Layout {
 	pub art: Art,
 	pub info: Info,
 	pub terminal: Terminal,
+	config: LayoutConfig,
+	/// Whether to fit to the terminal. `--logo` and plain output only print
+	/// one of the art and info, so there's nothing to fit together.
+	fit: bool,
+	/// The custom `layout.lua`, if any.
+	script: Option<PathBuf>,
+	/// The art before it's fit to the terminal, and `--width` and
+	/// `--height`, for laying out again with `redo`.
+	unfit: Art,
+	size: (Option<i32>, Option<i32>),
 }
 
 impl Layout {
-	pub fn new(args: &Arguments) -> errors::Result<Self> {
-		let mut info = Info::new()?;
-		let art = Art::new(&mut info, args)?;
-		let terminal = Terminal::new();
+	pub fn new(args: &Arguments, config: &Config) -> errors::Result<Self> {
+		let mut info = Info::new(config, &args.paths)?;
+		let mut art = Art::new(&mut info, args)?;
+		let picture = args.ascii_from_image.clone().or_else(|| config.logo.ascii_from_image.as_ref().map(|v| args.paths.resolve(v)));
+		if let Some(path) = picture {
+			art.use_picture(&path, u32::from(config.logo.ascii_width), config.logo.ascii_style)?;
+		}
+		if let Some(effect) = args.art_effect.as_ref().or(config.logo.effect.as_ref()) {
+			let mut palette = Vec::with_capacity(4);
+			for color in info.distro.colors.all() {
+				if !palette.contains(&color) { palette.push(color); }
+			}
+			art.apply_effect(effect, &palette);
+		}
+		// Images are drawn over the built-in layout's output, so custom
+		// layouts, which could put the art anywhere, keep the ASCII art.
+		let image = args.image.clone().or_else(|| config.logo.image.as_ref().map(|v| args.paths.resolve(v)));
+		if let Some(path) = image.filter(|_| args.paths.layout().is_none() && !args.color.plain) {
+			art.use_image(&path, config.logo.protocol)?;
+		}
+		let terminal = Terminal::new(args.width, args.height);
 		Ok(Layout {
+			unfit: art.clone(),
 			art,
 			info,
 			terminal,
+			config: config.layout.clone(),
+			fit: config.layout.fit && !args.logo && !args.color.plain,
+			script: args.paths.layout(),
+			size: (args.width, args.height),
 		})
 	}
+
+	/// Lays out again at the terminal's current size, for `--watch`, after
+	/// re-collecting the info if `collect` is set.
+	pub fn redo(&mut self, collect: bool) -> errors::Result<()> {
+		if collect { self.info.refresh()?; }
+		self.art = self.unfit.clone();
+		self.terminal = Terminal::new(self.size.0, self.size.1);
+		self.prep()
+	}
+
+	/// The text to print: the output of the custom `layout.lua` if there is
+	/// one, and otherwise the art and info laid out by `compose`. The
+	/// built-in layout is the same as `print(compose{})`, so it doesn't need
+	/// Lua.
+	pub fn render(&self) -> errors::Result<String> {
+		let script = match &self.script {
+			Some(v) => v,
+			None => return Ok(format!("{}\n", self.compose())),
+		};
+		let file = fs::read_to_string(script).map_err(|e| {
+			errors::FreshfetchError::Io(script.to_string_lossy().into_owned(), e.to_string())
+		})?;
+		let mut ctx = Lua::new();
+		ctx.load(PRINT).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		ctx.load(ANSI).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		utils::dimensions::inject(&mut ctx)?;
+		self.inject(&mut ctx)?;
+		ctx.load(&file).exec().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		let output: String = ctx.globals().get("__freshfetch__").map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+		Ok(output)
+	}
+
+	/// How to compose the art and info, from `[layout]`. Lines are cut off at
+	/// the terminal's width when `fit` is on.
+	pub fn options(&self) -> Options {
+		let bounded = self.terminal.width != i32::MAX;
+		Options {
+			position: self.config.art,
+			padding: usize::from(self.config.padding),
+			gap: usize::from(self.config.gap),
+			center: self.config.center,
+			width: (self.config.fit && bounded).then_some(self.terminal.width.max(0) as usize),
+		}
+	}
+
+	/// The art and info laid out by `compose`, or just the art for `--logo`.
+	pub fn compose(&self) -> String {
+		compose::compose(self.art.text(), self.shown_info(), &self.options())
+	}
+
+	/// Draws the image logo, if there is one, over the blank space left for
+	/// it in `rendered`, which must have just been printed, up to and
+	/// including its last newline.
+	pub fn draw_image(&self, rendered: &str) -> errors::Result<()> {
+		let image = match self.art.image() {
+			Some(v) => v,
+			None => return Ok(()),
+		};
+		let (x, y) = compose::origin(self.art.text(), self.shown_info(), &self.options());
+		let lines = rendered.matches('\n').count();
+		image.draw(x.min(u16::MAX as usize) as u16, lines.saturating_sub(y).min(u16::MAX as usize) as u16)
+	}
+
+	fn shown_info(&self) -> &str {
+		if self.art.is_logo() { "" } else { self.info.rendered() }
+	}
+
+	/// Fits the art and info into the terminal's width when they're too wide
+	/// side by side: first with the art's `_small` variant, then without the
+	/// art, and finally by cutting off the info's lines.
+	fn fit(&mut self) {
+		let width = self.terminal.width;
+		if !self.fit || width == i32::MAX { return; }
+		let below = |v: Option<u16>| v.is_some_and(|v| width < i32::from(v));
+		let padding = i32::from(self.config.padding);
+		let gap = i32::from(self.config.gap);
+		let beside = self.config.art.is_beside();
+		// The columns left for the info.
+		let room = move |art: &Art| if art.is_hidden() || !beside {
+			width - padding
+		} else {
+			width - padding - art.width() - gap
+		};
+		let fits = move |art: &Art, info: i32| room(art) >= info && padding + art.width() <= width;
+		let info = self.info.width();
+		// The info can be cut down to `min_info_width` before the art has to go.
+		let needed = info.min(self.config.min_info_width.map_or(info, i32::from));
+
+		if below(self.config.small_art_below) || !fits(&self.art, info) {
+			self.art.shrink();
+		}
+		if below(self.config.no_art_below) || !fits(&self.art, needed) {
+			self.art.hide();
+		}
+		let room = room(&self.art);
+		if info > room {
+			self.info.truncate(room);
+		}
+	}
 }
 
 impl Inject for Layout {
@@ -36,12 +177,16 @@ impl Inject for Layout {
 		self.info.prep()?;
 		self.art.prep()?;
 		self.terminal.prep()?;
+		let rows = crate::utils::get_dimensions(self.info.rendered().trim_end_matches('\n')).1;
+		self.art.fit_image(rows as u32, self.terminal.cell);
+		self.fit();
         Ok(())
 	}
 	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
 		self.art.inject(lua)?;
 		self.terminal.inject(lua)?;
 		self.info.inject(lua)?;
+		compose::inject(lua, self.art.text(), self.shown_info(), &self.options())?;
         Ok(())
 	}
 }
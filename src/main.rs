@@ -1,38 +1,79 @@
 #[macro_use]
 pub(crate) extern crate lazy_static;
-pub(crate) extern crate chrono;
 pub(crate) extern crate clap;
+pub(crate) extern crate clap_complete;
+pub(crate) extern crate clap_mangen;
 
+pub(crate) extern crate image;
+pub(crate) extern crate libc;
 pub(crate) extern crate mlua;
 pub(crate) extern crate regex;
+pub(crate) extern crate schemars;
+pub(crate) extern crate serde_json;
+pub(crate) extern crate serde_yaml;
+pub(crate) extern crate signal_hook;
+pub(crate) extern crate strsim;
 pub(crate) extern crate sysinfo;
 pub(crate) extern crate term_size;
 pub(crate) extern crate uname;
+pub(crate) extern crate unicode_width;
 pub(crate) extern crate users;
 pub(crate) extern crate dirs;
+pub(crate) extern crate toml;
 
 pub(crate) mod art;
 pub(crate) mod assets;
+pub(crate) mod cli;
+pub(crate) mod color;
+pub(crate) mod compose;
+pub(crate) mod config;
 pub(crate) mod errors;
 pub(crate) mod info;
 pub(crate) mod layout;
 pub(crate) mod misc;
+pub(crate) mod output;
+pub(crate) mod paths;
+pub(crate) mod template;
 pub(crate) mod utils;
+pub(crate) mod watch;
 
-use clap::{Command, Arg};
+use clap::ColorChoice;
+use clap_complete::Shell;
 use mlua::prelude::*;
 
-use assets::defaults::LAYOUT;
-use assets::{ANSI, HELP, PRINT};
+use assets::defaults::{ ART, CONFIG, INFO, LAYOUT };
+use art::effect::{ Effect };
+use art::pack::{ self, Pack };
+use assets::ascii_art;
+use color::{ ColorMode };
+use config::Config;
+use output::Format;
+use template::Template;
+use info::distro::{ DistroColors };
+use paths::Paths;
 use layout::Layout;
 
-use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::env;
+use std::fs;
+use std::io::{ self, Write };
+use std::path::{ Path, PathBuf };
+use std::time::{ Duration };
 
 pub(crate) struct Arguments {
 	pub ascii_distro: Option<String>,
 	pub logo: bool,
-	pub json: bool,
+	pub image: Option<PathBuf>,
+	pub ascii_from_image: Option<PathBuf>,
+	pub art_effect: Option<Effect>,
+	pub verbose: bool,
+	pub color: ColorMode,
+	pub width: Option<i32>,
+	pub height: Option<i32>,
+	pub watch: Option<Duration>,
+	pub format: Option<Format>,
+	pub only: Option<Vec<String>>,
+	pub exclude: Vec<String>,
+	pub paths: Paths,
 }
 
 pub(crate) trait Inject {
@@ -47,93 +88,214 @@ fn main() {
 }
 
 fn run() -> errors::Result<()> {
-	let matches = Command::new("freshfetch")
-		.version("0.2.0")
-		.author("Jack Johannesen")
-		.about("A fresh take on neofetch.")
-		.override_help(HELP)
-		.arg(
-			Arg::new("ascii_distro")
-				.long("ascii_distro")
-				.short('a')
-				.num_args(1)
-				.value_name("ASCII_DISTRO"),
-		)
-		.arg(
-			Arg::new("logo")
-				.long("logo")
-				.short('l')
-				.action(clap::ArgAction::SetTrue),
-		)
-		.arg(
-			Arg::new("json")
-				.long("json")
-				.short('j')
-				.help("Output system info as JSON")
-				.action(clap::ArgAction::SetTrue),
-		)
-		.get_matches();
+	let matches = cli::command().get_matches();
+
+	if let Some(("completions", sub)) = matches.subcommand() {
+		let shell = *sub.get_one::<Shell>("shell").expect("`shell` is required");
+		clap_complete::generate(shell, &mut cli::command(), "freshfetch", &mut io::stdout());
+		return Ok(());
+	}
+
+	if matches.get_flag("man") {
+		return clap_mangen::Man::new(cli::command())
+			.render(&mut io::stdout())
+			.map_err(|e| errors::FreshfetchError::Io(String::from("stdout"), e.to_string()));
+	}
+
+	if matches.get_flag("json_schema") {
+		println!("{}", output::schema()?);
+		return Ok(());
+	}
+
+	let color = ColorMode::detect(matches.get_one::<ColorChoice>("color").copied().unwrap_or(ColorChoice::Auto));
 
 	let args = Arguments {
 		ascii_distro: matches.get_one::<String>("ascii_distro").cloned(),
 		logo: matches.get_flag("logo"),
-		json: matches.get_flag("json"),
+		image: matches.get_one::<PathBuf>("image").cloned(),
+		ascii_from_image: matches.get_one::<PathBuf>("ascii_from_image").cloned(),
+		art_effect: matches.get_one::<Effect>("art_effect").cloned(),
+		verbose: matches.get_flag("verbose"),
+		color,
+		width: matches.get_one::<i32>("width").copied(),
+		height: matches.get_one::<i32>("height").copied(),
+		watch: matches.get_one::<u64>("watch").map(|v| Duration::from_secs(*v)),
+		format: matches.get_one::<Format>("format")
+			.copied()
+			.or(if matches.get_flag("json") { Some(Format::Json) } else { None }),
+		only: matches.get_many::<String>("only").map(|v| v.cloned().collect()),
+		exclude: matches.get_many::<String>("exclude").map(|v| v.cloned().collect()).unwrap_or_default(),
+		paths: Paths::new(
+			matches.get_one::<PathBuf>("config_dir").cloned(),
+			matches.get_one::<String>("profile")
+				.cloned()
+				.or_else(|| env::var("FRESHFETCH_PROFILE").ok().filter(|v| !v.is_empty())),
+			matches.get_one::<PathBuf>("layout").cloned(),
+			matches.get_one::<PathBuf>("info").cloned(),
+			matches.get_one::<PathBuf>("art").cloned(),
+		)?,
 	};
 
-	// JSON output mode - bypass Lua rendering
-	if args.json {
-		return output_json();
+	if matches.get_flag("list_ascii") {
+		return list_ascii(&pack::load(&args.paths.art_packs())?);
+	}
+
+	if matches.contains_id("preview_ascii") {
+		let pattern = matches.get_one::<String>("preview_ascii").map(|v| v.as_str()).unwrap_or("");
+		return preview_ascii(pattern, &pack::load(&args.paths.art_packs())?, color);
+	}
+
+	if matches.contains_id("dump_defaults") {
+		let dir = matches.get_one::<PathBuf>("dump_defaults")
+			.cloned()
+			.or_else(|| args.paths.profile_dir.clone())
+			.unwrap_or_else(|| args.paths.config_dir.clone());
+		return dump_defaults(&dir, matches.get_flag("force"));
 	}
 
-	let mut ctx = Lua::new();
-    
-    // Set 'logo' global for Lua layouts
-    ctx.globals().set("logo", args.logo)?;
+	let mut config = Config::load(&args.paths)?;
+	config.restrict(args.only.as_deref(), &args.exclude)?;
 
-	ctx.load(PRINT).exec()?;
-	ctx.load(ANSI).exec()?;
+	// Template output - bypass Lua rendering
+	if let Some(template) = matches.get_one::<String>("template") {
+		let template = Template::parse(template).map_err(|e| {
+			errors::FreshfetchError::General(format!("Invalid template: {}", e))
+		})?;
+		let info = info::Info::new(&config, &args.paths)?;
+		return write_stdout(&format!("{}\n", output::render_template(&info, &template)?));
+	}
+
+	// Machine-readable output - bypass Lua rendering
+	if let Some(format) = args.format {
+		return output(&args, &config, format);
+	}
 
-	let mut layout = Layout::new(&args)?;
+	if args.watch.is_some() && args.color.plain {
+		return Err(errors::FreshfetchError::General(String::from(
+			"--watch redraws in place, so it needs a terminal. Use --color always to force it.")));
+	}
+
+	let mut layout = Layout::new(&args, &config)?;
 	layout.prep()?;
-	layout.inject(&mut ctx)?;
-
-	let layout_file = dirs::home_dir()
-		.unwrap_or_else(|| PathBuf::from("."))
-		.join(".config/freshfetch/layout.lua");
-
-	if layout_file.exists() {
-		let v = read_to_string(&layout_file).map_err(|e| {
-            errors::FreshfetchError::Io(layout_file.to_string_lossy().into_owned(), e.to_string())
-        })?;
-        
-        ctx.load(&v).exec()?;
-        
-        let output: String = ctx.globals().get("__freshfetch__")?;
-        
-        print!("{}", output);
-	} else {
-		ctx.load(LAYOUT).exec()?;
-        
-        let output: String = ctx.globals().get("__freshfetch__")?;
-        
-        print!("{}", output);
-	}
-    
-    Ok(())
+	if args.verbose {
+		eprintln!("ASCII art: {}", layout.art.source());
+	}
+
+	if args.color.plain {
+		let text = if args.logo { layout.art.text() } else { layout.info.rendered() };
+		return write_stdout(&format!("{}\n", utils::ansi::strip(text).trim_end_matches('\n')));
+	}
+
+	if let Some(interval) = args.watch {
+		return watch::run(layout, interval, args.color.depth);
+	}
+
+	let output = layout.render()?;
+	write_stdout(&color::downsample(&output, args.color.depth))?;
+	layout.draw_image(&output)
 }
 
-fn output_json() -> errors::Result<()> {
+fn output(args: &Arguments, config: &Config, format: Format) -> errors::Result<()> {
 	use info::Info;
 	
 	// Gather all info
-	let info = Info::new()?;
+	let info = Info::new(config, &args.paths)?;
 	
-	// Use serde_json for automatic serialization
-	let json = serde_json::to_string_pretty(&info).map_err(|e| {
-		errors::FreshfetchError::General(format!("Failed to serialize info to JSON: {}", e))
+	let rendered = output::render(&info, format)?;
+	if rendered.ends_with('\n') {
+		write_stdout(&rendered)
+	} else {
+		write_stdout(&format!("{}\n", rendered))
+	}
+}
+
+/// Writes `s` to stdout. A closed pipe, as in `freshfetch | head`, isn't
+/// an error.
+pub(crate) fn write_stdout(s: &str) -> errors::Result<()> {
+	let mut stdout = io::stdout().lock();
+	match stdout.write_all(s.as_bytes()).and_then(|_| stdout.flush()) {
+		Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+			Err(errors::FreshfetchError::Io(String::from("stdout"), e.to_string()))
+		}
+		_ => Ok(()),
+	}
+}
+
+fn dump_defaults(dir: &Path, force: bool) -> errors::Result<()> {
+	// The sample art.lua gets another name, since any art.lua replaces the
+	// distro's art. print.lua and ansi.lua are always the built-in ones, so
+	// they aren't written at all.
+	let files = [
+		("layout.lua", LAYOUT),
+		("info.lua", INFO),
+		("art.lua.example", ART),
+		("config.toml", CONFIG),
+	];
+
+	// Check everything up front, so that nothing is written if anything
+	// would be overwritten.
+	if !force {
+		let existing: Vec<String> = files.iter()
+			.map(|(name, _)| dir.join(name))
+			.filter(|path| path.exists())
+			.map(|path| path.to_string_lossy().into_owned())
+			.collect();
+		if !existing.is_empty() {
+			return Err(errors::FreshfetchError::General(format!(
+				"Refusing to overwrite existing files (use --force to overwrite them):\n{}",
+				existing.join("\n"))));
+		}
+	}
+
+	fs::create_dir_all(dir).map_err(|e| {
+		errors::FreshfetchError::Io(dir.to_string_lossy().into_owned(), e.to_string())
 	})?;
-	
-	println!("{}", json);
+	for (name, contents) in files.iter() {
+		let path = dir.join(name);
+		fs::write(&path, contents).map_err(|e| {
+			errors::FreshfetchError::Io(path.to_string_lossy().into_owned(), e.to_string())
+		})?;
+		write_stdout(&format!("Wrote {}\n", path.to_string_lossy()))?;
+	}
 	Ok(())
 }
 
+fn list_ascii(packs: &[Pack]) -> errors::Result<()> {
+	let entries = ascii_art::entries();
+	let mut rows: Vec<(&str, String, String)> = packs.iter()
+		.map(|v| (v.name.as_str(), v.aliases.join(", "), format!("{} ({})", v.matches().join(", "), v.path.display())))
+		.collect();
+	rows.extend(entries.iter().map(|e| (e.name, e.aliases.join(", "), e.matches.join(", "))));
+	let name_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0).max(4);
+	let alias_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0).max(7);
+	let mut to_print = format!("{:name_width$}  {:alias_width$}  MATCHES\n", "NAME", "ALIASES");
+	for (name, aliases, matches) in rows.iter() {
+		to_print.push_str(&format!("{:name_width$}  {:alias_width$}  {}\n", name, aliases, matches.trim_start()));
+	}
+	write_stdout(&to_print)
+}
+
+fn preview_ascii(pattern: &str, packs: &[Pack], color: ColorMode) -> errors::Result<()> {
+	let pattern = pattern.to_lowercase();
+	let named = |name: &str, aliases: &[&str]| name.contains(&pattern) || aliases.iter().any(|a| a.contains(&pattern));
+	let mut arts: Vec<(&str, &str, DistroColors)> = packs.iter()
+		.filter(|v| named(&v.name, &v.aliases.iter().map(String::as_str).collect::<Vec<&str>>()))
+		.map(|v| (v.name.as_str(), v.art.as_str(), DistroColors::from(v.colors)))
+		.collect();
+	for entry in ascii_art::entries().into_iter().filter(|e| named(e.name, &e.aliases)) {
+		let (art, palette) = ascii_art::get_cli(entry.name);
+		arts.push((entry.name, art, DistroColors::from(palette)));
+	}
+	if arts.is_empty() {
+		return Err(errors::FreshfetchError::General(format!("No ASCII art matches \"{}\".", pattern)));
+	}
+	for (name, art, colors) in arts.iter() {
+		let preview = format!(
+			"\u{001b}[1m{}{}\u{001b}[0m\n{}\u{001b}[0m\n{}███{}███{}███{}███\u{001b}[0m\n\n",
+			colors.primary, name,
+			art.trim_end_matches('\n'),
+			colors.primary, colors.secondary, colors.tertiary, colors.quaternary);
+		write_stdout(&color::downsample(&preview, color.depth))?;
+	}
+	Ok(())
+}
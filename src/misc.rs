@@ -1,8 +1,11 @@
+use crate::libc;
 use crate::term_size;
 use crate::mlua;
 
 use crate::errors;
 
+use std::env;
+
 use mlua::prelude::*;
 
 use crate::Inject;
@@ -10,24 +13,58 @@ use crate::Inject;
 pub(crate) struct Terminal {
 	pub width: i32,
 	pub height: i32,
+	/// Whether either dimension couldn't be found, and is `i32::MAX`.
+	pub unbounded: bool,
+	/// The size of a cell in pixels, if the terminal reports it.
+	pub cell: Option<(u16, u16)>,
 }
 
 impl Terminal {
-	pub fn new() -> Self {
-		let (w, h) = term_size::dimensions().expect("Failed to get terminal dimensions.");
+	/// Each dimension comes from `--width`/`--height` if given, then the
+	/// terminal, then `$COLUMNS`/`$LINES`. If none of those are available,
+	/// as under cron or in CI, it's unbounded.
+	pub fn new(width: Option<i32>, height: Option<i32>) -> Self {
+		let size = term_size::dimensions();
+		let from_env = |name: &str| {
+			env::var(name).ok().and_then(|v| v.trim().parse::<i32>().ok()).filter(|v| *v > 0)
+		};
+		let width = width
+			.or(size.map(|(w, _)| w as i32))
+			.or_else(|| from_env("COLUMNS"));
+		let height = height
+			.or(size.map(|(_, h)| h as i32))
+			.or_else(|| from_env("LINES"));
 		Terminal {
-			width: w as i32,
-			height: h as i32,
+			width: width.unwrap_or(i32::MAX),
+			height: height.unwrap_or(i32::MAX),
+			unbounded: width.is_none() || height.is_none(),
+			cell: cell_size(),
 		}
 	}
 }
 
+/// Asks the terminal on stdout for its size in pixels, and divides it into
+/// cells. Many terminals leave the pixel size as zero.
+fn cell_size() -> Option<(u16, u16)> {
+	// SAFETY: `winsize` is plain data, and `TIOCGWINSZ` only writes to it.
+	let size = unsafe {
+		let mut size: libc::winsize = std::mem::zeroed();
+		if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) != 0 { return None; }
+		size
+	};
+	if size.ws_col == 0 || size.ws_row == 0 || size.ws_xpixel == 0 || size.ws_ypixel == 0 {
+		return None;
+	}
+	Some((size.ws_xpixel / size.ws_col, size.ws_ypixel / size.ws_row)).filter(|(w, h)| *w > 0 && *h > 0)
+}
+
 impl Inject for Terminal {
 	fn inject(&self, lua: &mut Lua) -> errors::Result<()> {
 		let globals = lua.globals();
 		let t = lua.create_table().map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         t.set("width", self.width).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         t.set("height", self.height).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+        t.set("unbounded", self.unbounded).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         globals.set("terminal", t).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
         Ok(())
 	}
@@ -0,0 +1,196 @@
+use crate::clap;
+use crate::schemars;
+use crate::serde_json;
+use crate::serde_yaml;
+use crate::toml;
+
+use crate::errors;
+use crate::info;
+use crate::template;
+
+use clap::{ ValueEnum };
+use schemars::{ JsonSchema };
+use serde::{ Serialize };
+use serde_json::{ Map, Value };
+
+use info::{ Info };
+use template::{ Template };
+
+/// The version of the output's structure. It's bumped whenever a field is
+/// renamed, removed, or changes its type or unit. Adding a field doesn't bump
+/// it, so consumers should ignore fields they don't know.
+pub(crate) const SCHEMA_VERSION: u32 = 2;
+
+/// What every format serialises: `Info`, tagged with `SCHEMA_VERSION`.
+#[derive(Serialize, JsonSchema)]
+#[schemars(title = "freshfetch", description = "System information, as printed by `freshfetch --format`.")]
+struct Document<'a> {
+	/// Bumped whenever a field is renamed, removed, or changes its type or
+	/// unit.
+	schema_version: u32,
+	#[serde(flatten)]
+	info: &'a Info,
+}
+
+/// The machine-readable formats `--format` can print `Info` as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+	Json,
+	Yaml,
+	Toml,
+	/// `FF_`-prefixed `KEY='value'` lines that a shell can `eval`.
+	Env,
+	/// `key,value` rows with dotted keys.
+	Csv,
+}
+
+/// Serialises `info` as `format`.
+///
+/// Every format goes through the same `Serialize` impls. JSON and YAML keep
+/// the structure as is, including `null`s for modules that weren't
+/// collected. TOML has no null, so those are left out. `env` and `csv` are
+/// flat: nested fields are joined into one key per value, arrays are
+/// indexed from 0 (`gpu.0.name`, `temperature.sensors.1.temp_celsius`), and nulls
+/// are left out.
+pub(crate) fn render(info: &Info, format: Format) -> errors::Result<String> {
+	let fail = |e: String| errors::FreshfetchError::General(format!("Failed to serialize info: {}", e));
+	let document = Document { schema_version: SCHEMA_VERSION, info };
+	match format {
+		Format::Json => serde_json::to_string_pretty(&document).map_err(|e| fail(e.to_string())),
+		Format::Yaml => serde_yaml::to_string(&document).map_err(|e| fail(e.to_string())),
+		Format::Toml => {
+			let value = strip_nulls(to_value(&document)?).unwrap_or(Value::Object(Map::new()));
+			toml::to_string(&value).map_err(|e| fail(e.to_string()))
+		}
+		Format::Env => {
+			let mut to_return = String::new();
+			for (path, value) in flatten(&to_value(&document)?) {
+				let key: String = path.join("_")
+					.chars()
+					.map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+					.collect();
+				to_return.push_str(&format!("FF_{}={}\n", key, shell_quote(&value)));
+			}
+			Ok(to_return)
+		}
+		Format::Csv => {
+			let mut to_return = String::from("key,value\r\n");
+			for (path, value) in flatten(&to_value(&document)?) {
+				to_return.push_str(&format!("{},{}\r\n", csv_quote(&path.join(".")), csv_quote(&value)));
+			}
+			Ok(to_return)
+		}
+	}
+}
+
+/// Fills in `template` from the same tree `render` serialises.
+pub(crate) fn render_template(info: &Info, template: &Template) -> errors::Result<String> {
+	let document = Document { schema_version: SCHEMA_VERSION, info };
+	template.render(&to_value(&document)?).map_err(|e| {
+		errors::FreshfetchError::General(format!("Invalid template: {}", e))
+	})
+}
+
+/// The JSON Schema of what `render` outputs.
+pub(crate) fn schema() -> errors::Result<String> {
+	serde_json::to_string_pretty(&schemars::schema_for!(Document<'static>)).map_err(|e| {
+		errors::FreshfetchError::General(format!("Failed to serialize the JSON schema: {}", e))
+	})
+}
+
+fn to_value(document: &Document) -> errors::Result<Value> {
+	// This goes through a string rather than `serde_json::to_value`, which
+	// widens `f32`s to `f64`s and so prints `3.8` as `3.799999952316284`.
+	serde_json::to_string(document)
+		.and_then(|v| serde_json::from_str(&v))
+		.map_err(|e| {
+			errors::FreshfetchError::General(format!("Failed to serialize info: {}", e))
+		})
+}
+
+/// Removes nulls from objects and arrays, returning `None` if `value` itself
+/// is null.
+fn strip_nulls(value: Value) -> Option<Value> {
+	match value {
+		Value::Null => None,
+		Value::Array(v) => Some(Value::Array(v.into_iter().filter_map(strip_nulls).collect())),
+		Value::Object(v) => Some(Value::Object(v.into_iter()
+			.filter_map(|(k, v)| strip_nulls(v).map(|v| (k, v)))
+			.collect())),
+		v => Some(v),
+	}
+}
+
+/// Flattens `value` into `(path, scalar)` pairs, in serialisation order.
+fn flatten(value: &Value) -> Vec<(Vec<String>, String)> {
+	fn walk(value: &Value, path: &mut Vec<String>, out: &mut Vec<(Vec<String>, String)>) {
+		match value {
+			Value::Null => (),
+			Value::Bool(v) => out.push((path.clone(), v.to_string())),
+			Value::Number(v) => out.push((path.clone(), v.to_string())),
+			Value::String(v) => out.push((path.clone(), v.clone())),
+			Value::Array(v) => {
+				for (i, v) in v.iter().enumerate() {
+					path.push(i.to_string());
+					walk(v, path, out);
+					path.pop();
+				}
+			}
+			Value::Object(v) => {
+				for (k, v) in v.iter() {
+					path.push(k.clone());
+					walk(v, path, out);
+					path.pop();
+				}
+			}
+		}
+	}
+	let mut to_return = Vec::new();
+	walk(value, &mut Vec::new(), &mut to_return);
+	to_return
+}
+
+/// Quotes `value` for POSIX shells.
+fn shell_quote(value: &str) -> String {
+	format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Quotes `value` per RFC 4180, if it needs it.
+fn csv_quote(value: &str) -> String {
+	if value.contains([',', '"', '\r', '\n']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		String::from(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn flattens_nested_values() {
+		let value = serde_json::json!({
+			"cpu": { "name": "Ryzen", "cores": 8 },
+			"gpu": [ { "name": "a" }, { "name": "b" } ],
+			"battery": null,
+		});
+		let got: Vec<(String, String)> = flatten(&value)
+			.into_iter()
+			.map(|(p, v)| (p.join("."), v))
+			.collect();
+		assert_eq!(got, vec![
+			(String::from("cpu.name"), String::from("Ryzen")),
+			(String::from("cpu.cores"), String::from("8")),
+			(String::from("gpu.0.name"), String::from("a")),
+			(String::from("gpu.1.name"), String::from("b")),
+		]);
+	}
+
+	#[test]
+	fn quotes() {
+		assert_eq!(shell_quote("it's"), "'it'\\''s'");
+		assert_eq!(csv_quote("plain"), "plain");
+		assert_eq!(csv_quote("a,\"b\""), "\"a,\"\"b\"\"\"");
+	}
+}
@@ -0,0 +1,147 @@
+use crate::errors;
+
+use std::env;
+use std::path::{ Path, PathBuf };
+
+/// Resolves where freshfetch's configuration files live.
+///
+/// Each file is looked up in this order:
+/// 1. Its own flag (`--layout`, `--info`, `--art`).
+/// 2. The profile directory, `<config dir>/profiles/<name>`, if a profile
+///    was selected.
+/// 3. The config directory, which is `--config-dir` if given, otherwise
+///    `$XDG_CONFIG_HOME/freshfetch`, otherwise `~/.config/freshfetch`.
+/// 4. The built-in default.
+#[derive(Clone, Debug)]
+pub(crate) struct Paths {
+	pub config_dir: PathBuf,
+	pub profile_dir: Option<PathBuf>,
+	layout: Option<PathBuf>,
+	info: Option<PathBuf>,
+	art: Option<PathBuf>,
+}
+
+impl Paths {
+	pub fn new(
+		config_dir: Option<PathBuf>,
+		profile: Option<String>,
+		layout: Option<PathBuf>,
+		info: Option<PathBuf>,
+		art: Option<PathBuf>,
+	) -> errors::Result<Self> {
+		// Files that were asked for explicitly must exist, rather than
+		// silently falling back to the defaults.
+		for file in [&layout, &info, &art].iter().copied().flatten() {
+			if !file.is_file() {
+				return Err(errors::FreshfetchError::Io(
+					file.to_string_lossy().into_owned(),
+					String::from("No such file."),
+				));
+			}
+		}
+		let config_dir = config_dir.unwrap_or_else(default_config_dir);
+		let profile_dir = match profile {
+			Some(name) => {
+				if name.is_empty() || name.contains(std::path::is_separator) || name == "." || name == ".." {
+					return Err(errors::FreshfetchError::General(format!("Invalid profile name \"{}\".", name)));
+				}
+				Some(config_dir.join("profiles").join(&name))
+			}
+			None => None,
+		};
+		Ok(Paths {
+			config_dir,
+			profile_dir,
+			layout,
+			info,
+			art,
+		})
+	}
+
+	/// Fails if a profile was selected but its directory doesn't exist.
+	/// Checked only before the profile's files are read, so that commands
+	/// which don't read them, like `--dump-defaults`, which can create it,
+	/// still work.
+	pub fn check_profile(&self) -> errors::Result<()> {
+		match &self.profile_dir {
+			Some(dir) if !dir.is_dir() => Err(errors::FreshfetchError::General(format!(
+				"The profile \"{}\" doesn't exist. Expected a directory at \"{}\".",
+				dir.file_name().unwrap_or_default().to_string_lossy(),
+				dir.to_string_lossy()))),
+			_ => Ok(()),
+		}
+	}
+
+	/// Every `config.toml` that exists, in the order they should be merged:
+	/// the config directory's first, then the profile's.
+	pub fn configs(&self) -> Vec<PathBuf> {
+		let mut to_return = Vec::new();
+		to_return.extend(existing(self.config_dir.join("config.toml")));
+		if let Some(dir) = &self.profile_dir {
+			to_return.extend(existing(dir.join("config.toml")));
+		}
+		to_return
+	}
+
+	/// The Lua layout to use instead of the built-in one, if any.
+	pub fn layout(&self) -> Option<PathBuf> {
+		self.layout.clone().or_else(|| self.lookup("layout.lua"))
+	}
+
+	/// The Lua info script to use instead of the built-in one, if any.
+	pub fn info(&self) -> Option<PathBuf> {
+		self.info.clone().or_else(|| self.lookup("info.lua"))
+	}
+
+	/// The Lua art script to use instead of the distro's ASCII art, if any.
+	pub fn art(&self) -> Option<PathBuf> {
+		self.art.clone().or_else(|| self.lookup("art.lua"))
+	}
+
+	/// The art pack directories that exist, most important first: the
+	/// profile's `ascii` directory, then the config directory's.
+	pub fn art_packs(&self) -> Vec<PathBuf> {
+		self.profile_dir
+			.iter()
+			.chain(std::iter::once(&self.config_dir))
+			.map(|dir| dir.join("ascii"))
+			.filter(|dir| dir.is_dir())
+			.collect()
+	}
+
+	/// Resolves a path from `config.toml`: `~/` is the home directory, and
+	/// relative paths are relative to the config directory.
+	pub fn resolve(&self, path: &Path) -> PathBuf {
+		match (path.strip_prefix("~"), dirs::home_dir()) {
+			(Ok(rest), Some(home)) => home.join(rest),
+			_ => self.config_dir.join(path),
+		}
+	}
+
+	/// Finds `name` in the profile directory, falling back to the config
+	/// directory.
+	fn lookup(&self, name: &str) -> Option<PathBuf> {
+		self.profile_dir
+			.as_ref()
+			.and_then(|dir| existing(dir.join(name)))
+			.or_else(|| existing(self.config_dir.join(name)))
+	}
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+	if path.is_file() { Some(path) } else { None }
+}
+
+fn default_config_dir() -> PathBuf {
+	// Per the XDG base directory spec, relative paths are ignored.
+	let base = env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.filter(|p| p.is_absolute())
+		.unwrap_or_else(|| {
+			dirs::home_dir()
+				.unwrap_or_else(|| PathBuf::from("."))
+				.join(".config")
+		});
+	base.join("freshfetch")
+}
+
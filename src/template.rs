@@ -0,0 +1,315 @@
+use crate::serde_json;
+
+use serde_json::{ Value };
+
+/// A parsed `--template`.
+///
+/// Text is copied as is, except for fields in braces, which are replaced by
+/// values from the serialised `Info` tree:
+///
+/// * `{cpu.name}` looks up a dotted path. Arrays are indexed with
+///   `{gpu[0].name}` or `{gpu.0.name}`.
+/// * A key without its unit suffix finds the suffixed one, so
+///   `{memory.used}` is `memory.used_bytes`. `{memory.max}` is another name
+///   for `{memory.total}`.
+/// * `{memory.used:GiB}` converts the value to another unit, rounded to one
+///   decimal place. `{memory.used:GiB.2}` picks the number of places.
+/// * `{battery.capacity_percent|none}` prints `none` if the module wasn't
+///   collected or has no such value.
+/// * `{{` and `}}` are literal braces.
+#[derive(Debug)]
+pub(crate) struct Template ( Vec<Part> );
+
+#[derive(Debug)]
+enum Part {
+	Text(String),
+	Field(Field),
+}
+
+#[derive(Debug)]
+struct Field {
+	source: String,
+	path: Vec<Segment>,
+	unit: Option<(Unit, usize)>,
+	default: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+	Key(String),
+	Index(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Dimension {
+	Bytes,
+	Frequency,
+	Time,
+	Temperature,
+}
+
+/// A unit, as its dimension and how to convert it to that dimension's base
+/// unit (bytes, hertz, seconds, degrees Celsius): `base = value * scale +
+/// offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Unit ( Dimension, f64, f64 );
+
+static UNITS: &[(&str, Unit)] = &[
+	("B", Unit(Dimension::Bytes, 1.0, 0.0)),
+	("kB", Unit(Dimension::Bytes, 1e3, 0.0)),
+	("MB", Unit(Dimension::Bytes, 1e6, 0.0)),
+	("GB", Unit(Dimension::Bytes, 1e9, 0.0)),
+	("TB", Unit(Dimension::Bytes, 1e12, 0.0)),
+	("KiB", Unit(Dimension::Bytes, 1024.0, 0.0)),
+	("MiB", Unit(Dimension::Bytes, 1048576.0, 0.0)),
+	("GiB", Unit(Dimension::Bytes, 1073741824.0, 0.0)),
+	("TiB", Unit(Dimension::Bytes, 1099511627776.0, 0.0)),
+	("Hz", Unit(Dimension::Frequency, 1.0, 0.0)),
+	("kHz", Unit(Dimension::Frequency, 1e3, 0.0)),
+	("MHz", Unit(Dimension::Frequency, 1e6, 0.0)),
+	("GHz", Unit(Dimension::Frequency, 1e9, 0.0)),
+	("s", Unit(Dimension::Time, 1.0, 0.0)),
+	("min", Unit(Dimension::Time, 60.0, 0.0)),
+	("h", Unit(Dimension::Time, 3600.0, 0.0)),
+	("d", Unit(Dimension::Time, 86400.0, 0.0)),
+	("C", Unit(Dimension::Temperature, 1.0, 0.0)),
+	("F", Unit(Dimension::Temperature, 5.0 / 9.0, -160.0 / 9.0)),
+	("K", Unit(Dimension::Temperature, 1.0, -273.15)),
+];
+
+/// The unit suffixes used by the structured output, and what they mean.
+static SUFFIXES: &[(&str, Unit)] = &[
+	("_bytes", Unit(Dimension::Bytes, 1.0, 0.0)),
+	("_ghz", Unit(Dimension::Frequency, 1e9, 0.0)),
+	("_hz", Unit(Dimension::Frequency, 1.0, 0.0)),
+	("_celsius", Unit(Dimension::Temperature, 1.0, 0.0)),
+	("seconds", Unit(Dimension::Time, 1.0, 0.0)),
+];
+
+/// Other names a key can be looked up by, as `(alias, key)`.
+static ALIASES: &[(&str, &str)] = &[
+	("max", "total"),
+];
+
+impl Template {
+	pub fn parse(template: &str) -> Result<Self, String> {
+		let mut parts = Vec::new();
+		let mut text = String::new();
+		let mut chars = template.chars().peekable();
+		while let Some(c) = chars.next() {
+			match c {
+				'{' if chars.peek() == Some(&'{') => { chars.next(); text.push('{'); }
+				'}' if chars.peek() == Some(&'}') => { chars.next(); text.push('}'); }
+				'{' => {
+					let mut inner = String::new();
+					loop {
+						match chars.next() {
+							Some('}') => break,
+							Some(c) => inner.push(c),
+							None => return Err(format!("Unclosed \"{{{}\".", inner)),
+						}
+					}
+					if !text.is_empty() { parts.push(Part::Text(std::mem::take(&mut text))); }
+					parts.push(Part::Field(Field::parse(&inner)?));
+				}
+				'}' => return Err(String::from("Unmatched \"}\". Use \"}}\" for a literal brace.")),
+				c => text.push(c),
+			}
+		}
+		if !text.is_empty() { parts.push(Part::Text(text)); }
+		Ok(Template(parts))
+	}
+
+	pub fn render(&self, root: &Value) -> Result<String, String> {
+		let mut to_return = String::new();
+		for part in self.0.iter() {
+			match part {
+				Part::Text(v) => to_return.push_str(v),
+				Part::Field(v) => to_return.push_str(&v.render(root)?),
+			}
+		}
+		Ok(to_return)
+	}
+}
+
+impl Field {
+	fn parse(source: &str) -> Result<Self, String> {
+		let (rest, default) = match source.split_once('|') {
+			Some((rest, default)) => (rest, Some(String::from(default))),
+			None => (source, None),
+		};
+		let (path, unit) = match rest.split_once(':') {
+			Some((path, unit)) => (path, Some(parse_unit(unit.trim())?)),
+			None => (rest, None),
+		};
+		Ok(Field {
+			source: String::from(source),
+			path: parse_path(path.trim())?,
+			unit,
+			default,
+		})
+	}
+
+	fn render(&self, root: &Value) -> Result<String, String> {
+		let (value, source_unit) = match lookup(root, &self.path)? {
+			Some(v) => v,
+			None => return Ok(self.default.clone().unwrap_or_default()),
+		};
+		match self.unit {
+			None => Ok(scalar(value)),
+			Some((to, places)) => {
+				let from = source_unit.ok_or_else(|| {
+					format!("\"{}\" has no unit to convert from.", self.source)
+				})?;
+				let value = value.as_f64().ok_or_else(|| {
+					format!("\"{}\" isn't a number.", self.source)
+				})?;
+				Ok(format!("{:.*}", places, convert(value, from, to)?))
+			}
+		}
+	}
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+	let invalid = || format!("Invalid path \"{}\".", path);
+	let mut to_return = Vec::new();
+	for part in path.split('.') {
+		let (key, mut indices) = match part.find('[') {
+			Some(i) => (&part[..i], &part[i..]),
+			None => (part, ""),
+		};
+		if key.is_empty() && indices.is_empty() { return Err(invalid()); }
+		if !key.is_empty() {
+			if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') { return Err(invalid()); }
+			to_return.push(Segment::Key(String::from(key)));
+		}
+		while !indices.is_empty() {
+			let end = indices.find(']').ok_or_else(invalid)?;
+			let index = indices[1..end].parse::<usize>().map_err(|_| invalid())?;
+			to_return.push(Segment::Index(index));
+			indices = &indices[end + 1..];
+			if !indices.is_empty() && !indices.starts_with('[') { return Err(invalid()); }
+		}
+	}
+	Ok(to_return)
+}
+
+fn parse_unit(unit: &str) -> Result<(Unit, usize), String> {
+	let (name, places) = match unit.split_once('.') {
+		Some((name, places)) => (name, places.parse::<usize>().map_err(|_| {
+			format!("Invalid number of decimal places \"{}\".", places)
+		})?),
+		None => (unit, 1),
+	};
+	UNITS.iter()
+		.find(|(n, _)| *n == name)
+		.or_else(|| UNITS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)))
+		.map(|(_, u)| (*u, places))
+		.ok_or_else(|| format!(
+			"Unknown unit \"{}\". Expected one of: {}.",
+			name,
+			UNITS.iter().map(|(n, _)| *n).collect::<Vec<&str>>().join(", ")))
+}
+
+/// Follows `path` from `root`. Returns `None` if it runs into a null or off
+/// the end of an array, and an error if a key doesn't exist, since that's
+/// most likely a typo.
+fn lookup<'a>(root: &'a Value, path: &[Segment]) -> Result<Option<(&'a Value, Option<Unit>)>, String> {
+	let mut value = root;
+	let mut unit = None;
+	for segment in path.iter() {
+		value = match (value, segment) {
+			(Value::Null, _) => return Ok(None),
+			(Value::Array(v), Segment::Index(i)) => match v.get(*i) {
+				Some(v) => v,
+				None => return Ok(None),
+			},
+			(Value::Array(v), Segment::Key(k)) if k.parse::<usize>().is_ok() => {
+				match v.get(k.parse::<usize>().unwrap_or_default()) {
+					Some(v) => v,
+					None => return Ok(None),
+				}
+			}
+			(Value::Object(v), Segment::Key(k)) => {
+				let find = |k: &str| v.get_key_value(k).or_else(|| {
+					SUFFIXES.iter().find_map(|(suffix, _)| v.get_key_value(format!("{}{}", k, suffix).as_str()))
+				});
+				let found = find(k).or_else(|| {
+					ALIASES.iter().find(|(alias, _)| alias == k).and_then(|(_, key)| find(key))
+				});
+				match found {
+					Some((k, v)) => {
+						unit = SUFFIXES.iter().find(|(suffix, _)| k.ends_with(suffix)).map(|(_, u)| *u);
+						v
+					}
+					None => return Err(format!("Unknown field \"{}\".", k)),
+				}
+			}
+			(_, Segment::Index(i)) => return Err(format!("Can't index into a non-array with [{}].", i)),
+			(_, Segment::Key(k)) => return Err(format!("Can't look up \"{}\" in a non-object.", k)),
+		};
+	}
+	if value.is_null() { Ok(None) } else { Ok(Some((value, unit))) }
+}
+
+fn convert(value: f64, from: Unit, to: Unit) -> Result<f64, String> {
+	if from.0 != to.0 {
+		return Err(format!("Can't convert {:?} to {:?}.", from.0, to.0));
+	}
+	Ok((value * from.1 + from.2 - to.2) / to.1)
+}
+
+/// Formats a value that isn't being converted. Arrays of scalars are joined
+/// with commas, and anything else is printed as JSON.
+fn scalar(value: &Value) -> String {
+	match value {
+		Value::String(v) => v.clone(),
+		Value::Array(v) if v.iter().all(|v| !v.is_array() && !v.is_object()) => {
+			v.iter().map(scalar).collect::<Vec<String>>().join(", ")
+		}
+		v => v.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn render(template: &str) -> Result<String, String> {
+		let root = serde_json::json!({
+			"distro": { "short_name": "Arch Linux" },
+			"cpu": { "name": "Ryzen 7", "cores": 16, "freq_ghz": 3.8 },
+			"gpu": [ { "name": "RX 6700" }, { "name": "iGPU" } ],
+			"memory": { "total_bytes": 17179869184u64, "used_bytes": 4294967296u64 },
+			"uptime": { "seconds": 5400 },
+			"battery": null,
+		});
+		Template::parse(template)?.render(&root)
+	}
+
+	#[test]
+	fn paths() {
+		assert_eq!(render("{distro.short_name} | {cpu.name} {cpu.cores}c").unwrap(), "Arch Linux | Ryzen 7 16c");
+		assert_eq!(render("{gpu[1].name} {gpu.0.name}").unwrap(), "iGPU RX 6700");
+		assert_eq!(render("{{{cpu.cores}}}").unwrap(), "{16}");
+		assert!(render("{cpu.nmae}").is_err());
+		assert!(render("{cpu.name").is_err());
+	}
+
+	#[test]
+	fn units() {
+		assert_eq!(render("{memory.used:GiB}/{memory.total:GiB.0}").unwrap(), "4.0/16");
+		assert_eq!(render("{memory.used:GiB}/{memory.max:GiB}").unwrap(), "4.0/16.0");
+		assert_eq!(render("{cpu.freq:MHz.0}").unwrap(), "3800");
+		assert_eq!(render("{uptime.seconds:h.2}").unwrap(), "1.50");
+		assert!(render("{cpu.cores:GiB}").is_err());
+		assert!(render("{memory.used:GHz}").is_err());
+	}
+
+	#[test]
+	fn defaults() {
+		assert_eq!(render("{battery.capacity_percent|AC}").unwrap(), "AC");
+		assert_eq!(render("{battery.capacity_percent}").unwrap(), "");
+		assert_eq!(render("{gpu[5].name|-}").unwrap(), "-");
+	}
+}
@@ -0,0 +1,130 @@
+use crate::unicode_width;
+
+use unicode_width::{ UnicodeWidthChar, UnicodeWidthStr };
+
+/// A piece of a string that may contain ANSI escape sequences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+	Text(&'a str),
+	/// A CSI sequence, like `\x1b[1;31m` or `\x1b[2A`. The parameters and
+	/// the final byte are split out, since SGR (`m`) sequences need them.
+	Csi { raw: &'a str, params: &'a str, end: char },
+	/// Any other escape sequence, like an OSC title or hyperlink.
+	Escape(&'a str),
+}
+
+/// Splits `s` into text and escape sequences.
+pub fn tokens(s: &str) -> Vec<Token<'_>> {
+	let bytes = s.as_bytes();
+	let mut to_return = Vec::new();
+	let mut text_start = 0;
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] != 0x1b {
+			i += 1;
+			continue;
+		}
+		let start = i;
+		let (end, token) = match bytes.get(i + 1) {
+			// CSI: parameters and intermediates, then a final byte in
+			// `@`..=`~`.
+			Some(b'[') => {
+				let mut j = i + 2;
+				while j < bytes.len() && (0x20..=0x3f).contains(&bytes[j]) { j += 1; }
+				match bytes.get(j) {
+					Some(c) if (0x40..=0x7e).contains(c) => (j + 1, Some(Token::Csi {
+						raw: &s[start..j + 1],
+						params: &s[start + 2..j],
+						end: *c as char,
+					})),
+					// Malformed, so it's treated as an opaque escape.
+					_ => (j, None),
+				}
+			}
+			// OSC: ends with BEL or ST (`\x1b\\`).
+			Some(b']') => {
+				let mut j = i + 2;
+				let end = loop {
+					match bytes.get(j) {
+						None => break j,
+						Some(0x07) => break j + 1,
+						Some(0x1b) if bytes.get(j + 1) == Some(&b'\\') => break j + 2,
+						Some(_) => j += 1,
+					}
+				};
+				(end, None)
+			}
+			// Two character sequences, like `\x1b7` (save cursor).
+			Some(_) => (i + 1 + s[i + 1..].chars().next().map_or(0, |c| c.len_utf8()), None),
+			None => (i + 1, None),
+		};
+		if text_start < start { to_return.push(Token::Text(&s[text_start..start])); }
+		to_return.push(token.unwrap_or(Token::Escape(&s[start..end])));
+		text_start = end;
+		i = end;
+	}
+	if text_start < bytes.len() { to_return.push(Token::Text(&s[text_start..])); }
+	to_return
+}
+
+/// Removes every ANSI escape sequence from `s`.
+pub fn strip(s: &str) -> String {
+	tokens(s)
+		.into_iter()
+		.filter_map(|t| if let Token::Text(v) = t { Some(v) } else { None })
+		.collect()
+}
+
+/// The number of columns `line` takes up in a terminal. Escape sequences
+/// take none, and wide characters, like CJK and most emoji, take two.
+pub fn width(line: &str) -> usize {
+	tokens(line)
+		.into_iter()
+		.map(|t| if let Token::Text(v) = t { v.width() } else { 0 })
+		.sum()
+}
+
+/// Cuts `line` down to `width` columns. Escape sequences are all kept, even
+/// after the cut, so colors that are reset later still are. A wide character
+/// that would straddle the edge is dropped.
+pub fn truncate(line: &str, width: usize) -> String {
+	let mut to_return = String::with_capacity(line.len());
+	let mut left = width;
+	for token in tokens(line) {
+		match token {
+			Token::Text(v) => {
+				for c in v.chars() {
+					let w = c.width().unwrap_or(0);
+					if w > left {
+						left = 0;
+						break;
+					}
+					left -= w;
+					to_return.push(c);
+				}
+			}
+			Token::Csi { raw, .. } | Token::Escape(raw) => to_return.push_str(raw),
+		}
+	}
+	to_return
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn measures_display_width() {
+		assert_eq!(width("\u{1b}[1;38;2;255;0;0mred\u{1b}[0m"), 3);
+		assert_eq!(width("\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\"), 4);
+		assert_eq!(width("\u{8a08}\u{7b97}\u{6a5f}"), 6);
+		assert_eq!(width("\u{1f980}!"), 3);
+	}
+
+	#[test]
+	fn truncates_by_display_width() {
+		assert_eq!(truncate("\u{1b}[31mabcdef\u{1b}[0m", 3), "\u{1b}[31mabc\u{1b}[0m");
+		assert_eq!(truncate("\u{8a08}\u{7b97}\u{6a5f}", 3), "\u{8a08}");
+		assert_eq!(truncate("ab", 5), "ab");
+	}
+}
@@ -1,22 +1,21 @@
-use crate::regex::Regex;
+use crate::mlua;
+
+use crate::errors;
+use crate::utils::ansi;
+
+use mlua::prelude::*;
 
 /// Calculates the visual width and height of a string, accounting for ANSI escape codes.
 /// 
-/// This function strips ANSI sequences before measuring the length of each line to ensure
-/// that the returned dimensions represent how the string will actually appear in the terminal.
+/// Escape sequences (colors, cursor movement, OSC hyperlinks) take no space, and wide
+/// characters such as CJK and emoji take two columns, so the returned dimensions represent
+/// how the string will actually appear in the terminal.
 pub fn get_dimensions(s: &str) -> (i32, i32) {
-    let plaintext = {
-        // This regex matches ANSI escape codes to strip them before measuring width.
-        // It's copied from the original art.rs and info/mod.rs logic.
-        let regex = Regex::new(r#"(?i) \[(?:[\d;]*\d+[a-z])"#).unwrap();
-        String::from(regex.replace_all(s, ""))
-    };
-
     let mut w = 0usize;
     let mut h = 0usize;
 
-    for line in plaintext.split('\n') {
-        let len = line.chars().count();
+    for line in s.split('\n') {
+        let len = ansi::width(line);
         if len > w {
             w = len;
         }
@@ -25,3 +24,22 @@ pub fn get_dimensions(s: &str) -> (i32, i32) {
 
     (w as i32, h as i32)
 }
+
+/// Injects `displayWidth(s)`, the columns the widest line of `s` takes up,
+/// and `truncate(s, n)`, which cuts every line of `s` down to `n` columns
+/// without breaking its escape sequences.
+pub fn inject(lua: &mut Lua) -> errors::Result<()> {
+    let globals = lua.globals();
+
+    let display_width = lua.create_function(|_, s: String| {
+        Ok(get_dimensions(&s).0)
+    }).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+    globals.set("displayWidth", display_width).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+    let truncate = lua.create_function(|_, (s, n): (String, usize)| {
+        Ok(s.split('\n').map(|line| ansi::truncate(line, n)).collect::<Vec<String>>().join("\n"))
+    }).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+    globals.set("truncate", truncate).map_err(|e| errors::FreshfetchError::Lua(e.to_string()))?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+pub mod ansi;
 pub mod which;
 pub mod dimensions;
 
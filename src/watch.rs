@@ -0,0 +1,95 @@
+use crate::signal_hook;
+
+use crate::color;
+use crate::errors;
+use crate::layout;
+use crate::utils;
+
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use std::thread;
+use std::time::{ Duration, Instant };
+
+use signal_hook::consts::{ SIGHUP, SIGINT, SIGTERM, SIGWINCH };
+use signal_hook::flag;
+
+use color::{ Depth };
+use layout::{ Layout };
+use utils::ansi;
+
+/// Switches to the alternate screen and hides the cursor, so the shell's
+/// scrollback is left alone.
+const ENTER: &str = "\u{1b}[?1049h\u{1b}[?25l";
+const LEAVE: &str = "\u{1b}[?25h\u{1b}[?1049l";
+
+/// How often to check for a resize or a signal to stop between redraws.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Redraws `layout` every `interval`, with the volatile modules collected
+/// again, and lays it out again whenever the terminal is resized. Runs until
+/// freshfetch gets SIGINT, SIGTERM or SIGHUP; a second one kills it, in case
+/// a script is stuck.
+pub(crate) fn run(mut layout: Layout, interval: Duration, depth: Depth) -> errors::Result<()> {
+	let resized = Arc::new(AtomicBool::new(false));
+	let quit = Arc::new(AtomicBool::new(false));
+	let register = |signal: i32, flag: &Arc<AtomicBool>, shutdown: bool| {
+		let registered = if shutdown {
+			flag::register_conditional_shutdown(signal, 1, Arc::clone(flag)).and_then(|_| flag::register(signal, Arc::clone(flag)))
+		} else {
+			flag::register(signal, Arc::clone(flag))
+		};
+		registered.map(|_| ()).map_err(|e| {
+			errors::FreshfetchError::General(format!("Failed to handle signal {}: {}", signal, e))
+		})
+	};
+	register(SIGWINCH, &resized, false)?;
+	for signal in [SIGINT, SIGTERM, SIGHUP] {
+		register(signal, &quit, true)?;
+	}
+
+	crate::write_stdout(ENTER)?;
+	let result = watch(&mut layout, interval, depth, &resized, &quit);
+	crate::write_stdout(LEAVE)?;
+	result
+}
+
+fn watch(layout: &mut Layout, interval: Duration, depth: Depth, resized: &AtomicBool, quit: &AtomicBool) -> errors::Result<()> {
+	let mut next = Instant::now() + interval;
+	loop {
+		draw(layout, depth)?;
+		loop {
+			if quit.load(Ordering::Relaxed) { return Ok(()); }
+			if resized.swap(false, Ordering::Relaxed) {
+				layout.redo(false)?;
+				break;
+			}
+			let now = Instant::now();
+			if now >= next {
+				layout.redo(true)?;
+				next = now + interval;
+				break;
+			}
+			thread::sleep(POLL.min(next - now));
+		}
+	}
+}
+
+/// Draws over the last frame from the top left corner, clearing what's left
+/// of every line and everything below. Lines are cut off at the terminal's
+/// edges, since anything that wraps or scrolls would shift the next frame.
+fn draw(layout: &Layout, depth: Depth) -> errors::Result<()> {
+	let text = color::downsample(&layout.render()?, depth);
+	let columns = layout.terminal.width.max(0) as usize;
+	let rows = (layout.terminal.height.max(1) - 1) as usize;
+	let mut frame = String::from("\u{1b}[H");
+	if let Some(image) = layout.art.image() {
+		frame.push_str(image.erase());
+	}
+	for line in text.trim_end_matches('\n').split('\n').take(rows) {
+		frame.push_str(&ansi::truncate(line, columns));
+		frame.push_str("\u{1b}[0m\u{1b}[K\n");
+	}
+	frame.push_str("\u{1b}[J");
+	crate::write_stdout(&frame)?;
+	layout.draw_image(&frame)
+}